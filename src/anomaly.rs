@@ -7,6 +7,7 @@ use rand::seq::SliceRandom;
 use std::slice::SliceIndex;
 use std::ops::Bound::*;
 use crate::paa;
+use crate::dist::{Proximity, Euclidean};
 
 struct _Index(usize, usize);
 
@@ -26,7 +27,7 @@ fn _index_from_range(bounds: impl RangeBounds<usize>, len: usize) -> _Index {
 /// - `sax_word_length` = 3
 /// - `alpha` = 3
 /// - `use_brute_force` = false
-pub struct Anomaly<'a, N: Float> {
+pub struct Anomaly<'a, N: Float, M: Proximity<N> = Euclidean> {
     data: &'a Vec<N>,
     discord_size: usize,
     sax_word_length: usize,
@@ -34,17 +35,65 @@ pub struct Anomaly<'a, N: Float> {
     algo: Algorithm,
     dim_reduce: usize,
     index: _Index,
+    last_algo: std::cell::Cell<Option<ChosenAlgorithm>>,
+    numerosity_reduction: bool,
+    fuzzy_match: Option<(f64, Vec<usize>)>,
+    metric: M,
 }
 
+#[derive(Clone, Copy)]
 pub enum Algorithm {
     Bruteforce,
     HOTSAX,
-    Squeezer(f64)
+    Squeezer(f64),
+    /// Approximate nearest-neighbor search backed by a Hierarchical Navigable Small World
+    /// graph over the z-normalized subsequences. Trades exactness for speed on long series:
+    /// `ef` is the candidate-set width used while querying, and `m` bounds the out-degree of
+    /// each node in the graph (doubled on the bottom layer). Distances and discord locations
+    /// may differ slightly from the exact algorithms.
+    HnswApprox { ef: usize, m: usize },
+    /// Exact nearest-neighbor search backed by a vantage-point tree over the z-normalized
+    /// subsequences. A running best radius prunes subtrees the triangle inequality proves
+    /// can't contain anything closer, so, unlike `HnswApprox`, results are exact as long as
+    /// the chosen `Proximity` metric obeys the triangle inequality. With a metric that doesn't
+    /// (such as `Dtw`), the pruning can skip the true nearest neighbour.
+    VpTree,
+    /// Clusters SAX words with the GSDMM (Movie Group Process) model instead of `Squeezer`'s
+    /// single-pass greedy heuristic. `k` is the maximum number of clusters to seed (the learned
+    /// count can be lower, since empty clusters die off during sampling), `alpha`/`beta` are the
+    /// Dirichlet concentration hyperparameters controlling how readily a word joins a cluster
+    /// based on popularity (`alpha`) versus symbol overlap (`beta`), and `maxit` is the number of
+    /// Gibbs sampling sweeps to run.
+    Gsdmm { k: usize, alpha: f64, beta: f64, maxit: usize },
+    /// Picks `Bruteforce`, `HOTSAX`, or `Squeezer` depending on how many candidate windows
+    /// (`data.len() - discord_size + 1`) there are to search. Below `threshold` candidates,
+    /// brute force is cheapest to set up; above it HOT SAX's trie/frequency machinery pays
+    /// for itself; and above `threshold * AUTO_SQUEEZER_SCALE` candidates the search
+    /// escalates further to the Squeezer-backed path. Use `Anomaly::last_algo` to see which
+    /// one was actually picked for the most recent call.
+    Auto { threshold: usize },
 }
 
-impl<'a, N: Float> Anomaly<'a, N> {
+/// Identifies which concrete algorithm `Algorithm::Auto` picked for the most recent
+/// `find_*` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChosenAlgorithm {
+    Bruteforce,
+    HOTSAX,
+    Squeezer,
+}
+
+/// Multiplier applied to `Algorithm::Auto`'s threshold to decide when the candidate count is
+/// large enough to escalate from HOT SAX to the Squeezer-backed search.
+const AUTO_SQUEEZER_SCALE: usize = 10;
 
-    /// Sets up the data and the discord size to be used.
+/// Squeezer similarity threshold used when `Algorithm::Auto` escalates to the Squeezer path.
+const AUTO_SQUEEZER_THRESHOLD: f64 = 0.85;
+
+impl<'a, N: Float + 'static> Anomaly<'a, N, Euclidean> {
+
+    /// Sets up the data and the discord size to be used, comparing subsequences with the
+    /// default [`Euclidean`] ("gaussian") distance.
     ///
     /// By default it uses:
     /// - `sax_word_length: 3`
@@ -52,6 +101,21 @@ impl<'a, N: Float> Anomaly<'a, N> {
     /// - `algo: Algorithm::HOTSAX`
     /// - `dim_reduce: 0` (disabled)
     pub fn with(data: &'a Vec<N>, discord_size: usize) -> Self {
+        Self::with_metric(data, discord_size, Euclidean)
+    }
+}
+
+impl<'a, N: Float, M: Proximity<N>> Anomaly<'a, N, M> {
+
+    /// Sets up the data and the discord size to be used, comparing subsequences with `metric`
+    /// instead of the default [`Euclidean`] distance.
+    ///
+    /// By default it uses:
+    /// - `sax_word_length: 3`
+    /// - `alpha: 3`
+    /// - `algo: Algorithm::HOTSAX`
+    /// - `dim_reduce: 0` (disabled)
+    pub fn with_metric(data: &'a Vec<N>, discord_size: usize, metric: M) -> Self {
         Self {
             data,
             discord_size,
@@ -59,7 +123,38 @@ impl<'a, N: Float> Anomaly<'a, N> {
             alpha: 3,
             algo: Algorithm::HOTSAX,
             dim_reduce: 0,
-            index: _index_from_range(.., data.len())
+            index: _index_from_range(.., data.len()),
+            last_algo: std::cell::Cell::new(None),
+            numerosity_reduction: false,
+            fuzzy_match: None,
+            metric,
+        }
+    }
+
+    /// Returns the concrete algorithm `Algorithm::Auto` picked for the most recent `find_*`
+    /// call, or `None` if `Auto` hasn't been used yet.
+    pub fn last_algo(&self) -> Option<ChosenAlgorithm> {
+        self.last_algo.get()
+    }
+
+    /// Resolves `self.algo` into a concrete, non-`Auto` algorithm given how many candidate
+    /// windows are being searched, recording the decision so it can be read back via
+    /// `last_algo`.
+    fn resolve_algo(&self, candidate_amnt: usize) -> Algorithm {
+        match self.algo {
+            Algorithm::Auto { threshold } => {
+                let (chosen, label) = if candidate_amnt < threshold {
+                    (Algorithm::Bruteforce, ChosenAlgorithm::Bruteforce)
+                } else if candidate_amnt < threshold * AUTO_SQUEEZER_SCALE {
+                    (Algorithm::HOTSAX, ChosenAlgorithm::HOTSAX)
+                } else {
+                    (Algorithm::Squeezer(AUTO_SQUEEZER_THRESHOLD), ChosenAlgorithm::Squeezer)
+                };
+
+                self.last_algo.set(Some(label));
+                chosen
+            },
+            other => other
         }
     }
 
@@ -88,34 +183,67 @@ impl<'a, N: Float> Anomaly<'a, N> {
         self
     }
 
-    /// Sets the alphabet size to be used. The only valid values should be in the range 3..=7.
+    /// Sets the alphabet size to be used. `3..=7` use the paper's hardcoded breakpoints; any
+    /// other value from 2 up to 159 falls back to breakpoints computed (and cached) from the
+    /// Gaussian quantile function, via `dim_reduction::sax`.
     ///
     /// ## Panics
-    /// - When `n` is set to an invalid value.
+    /// - When `n` is under 2. (A value over 159 is accepted here, but makes `dim_reduction::sax`
+    ///   panic once the search runs.)
     pub fn alpha(&mut self, n: usize) -> &mut Self {
-        if (n<3) | (n>7) {
-            panic!("Invalid setting for alphabet size ({}). Only values in 3-7 are supported.", n);
+        if n < 2 {
+            panic!("Invalid setting for alphabet size ({}). At least 2 is required.", n);
         }
 
         self.alpha = n;
         self
     }
 
+    /// Enables or disables numerosity reduction: when consecutive sliding windows map to the
+    /// identical SAX word, only the first occurrence is kept and the repeats are skipped before
+    /// the word stream is handed to whichever candidate-ordering step `algo` uses next (the
+    /// trie/frequency table for `Algorithm::HOTSAX`, or clustering for `Algorithm::Squeezer`
+    /// and `Algorithm::Gsdmm`). This collapses long runs of self-similar subsequences that would
+    /// otherwise dominate the candidate set and bias the outer-loop ordering. Disabled by
+    /// default.
+    pub fn numerosity_reduction(&mut self, enabled: bool) -> &mut Self {
+        self.numerosity_reduction = enabled;
+        self
+    }
+
+    /// Enables fuzzy SAX-word matching for `Algorithm::HOTSAX`'s frequency counting: instead
+    /// of counting exact-match occurrences, words are grouped with `fuzzy_match::fuzzy_cluster`
+    /// using `ngram_sizes`-long substrings, and a word's frequency becomes the size of its
+    /// cluster. This stops a rare-looking word that actually has many near-matches elsewhere
+    /// (a single-symbol jitter away) from being falsely promoted as a discord just because it
+    /// has no *exact* duplicates. `threshold = 1.0` degenerates to exact matching. Disabled
+    /// (`None`) by default.
+    pub fn fuzzy_match(&mut self, threshold: f64, ngram_sizes: Vec<usize>) -> &mut Self {
+        self.fuzzy_match = Some((threshold, ngram_sizes));
+        self
+    }
+}
+
+impl<'a, N: Float, M: Proximity<N> + Clone> Anomaly<'a, N, M> {
+
     /// Finds the largest discord. If one couldn't be found, this function returns a `None` instead.
     pub fn find_largest_discord(&self) -> Option<(f64, usize)> {
         let use_subslice = self.data.get(self.index.0..self.index.1).unwrap();
+        let candidate_amnt = use_subslice.len().saturating_sub(self.discord_size) + 1;
 
-        let discord = match self.algo {
+        let discord = match self.resolve_algo(candidate_amnt) {
             Algorithm::Bruteforce => {
                 if self.dim_reduce > 1 {
                     anomaly_internal::brute_force_best(
                         &paa(&use_subslice.to_vec(), self.dim_reduce),
-                        self.discord_size
+                        self.discord_size,
+                        &self.metric
                     ).map(|(dist, loc)| (dist, loc*((1000/self.dim_reduce) as usize)))
                 } else {
                     anomaly_internal::brute_force_best(
                         &use_subslice,
-                        self.discord_size
+                        self.discord_size,
+                        &self.metric
                     )
                 }
             },
@@ -124,7 +252,10 @@ impl<'a, N: Float> Anomaly<'a, N> {
                     &use_subslice,
                     self.discord_size,
                     self.sax_word_length,
-                    self.alpha
+                    self.alpha,
+                    self.numerosity_reduction,
+                    &self.fuzzy_match,
+                    &self.metric
                 )
             },
             Algorithm::Squeezer(threshold) => {
@@ -133,9 +264,42 @@ impl<'a, N: Float> Anomaly<'a, N> {
                     self.discord_size,
                     self.sax_word_length,
                     self.alpha,
-                    threshold
+                    threshold,
+                    self.numerosity_reduction,
+                    &self.metric
                 )
-            }
+            },
+            Algorithm::HnswApprox { ef, m } => {
+                anomaly_internal::hnsw_best(
+                    &use_subslice,
+                    self.discord_size,
+                    ef,
+                    m,
+                    &self.metric
+                )
+            },
+            Algorithm::VpTree => {
+                anomaly_internal::vptree_best(
+                    &use_subslice,
+                    self.discord_size,
+                    &self.metric
+                )
+            },
+            Algorithm::Gsdmm { k, alpha, beta, maxit } => {
+                anomaly_internal::gsdmm_best(
+                    &use_subslice,
+                    self.discord_size,
+                    self.sax_word_length,
+                    self.alpha,
+                    k,
+                    alpha,
+                    beta,
+                    maxit,
+                    self.numerosity_reduction,
+                    &self.metric
+                )
+            },
+            Algorithm::Auto { .. } => unreachable!("resolve_algo never returns Algorithm::Auto")
         };
 
         discord.map(|(dist, loc)| (dist, loc+self.index.0))
@@ -146,20 +310,23 @@ impl<'a, N: Float> Anomaly<'a, N> {
     pub fn find_n_largest_discords(&self, discord_amnt: usize) -> Vec<(f64, usize)> {
         let use_subslice = self.data.get(self.index.0..self.index.1)
             .expect(&format!("Couldn't retrieve subslice ({}..{})", self.index.0, self.index.1));
+        let candidate_amnt = use_subslice.len().saturating_sub(self.discord_size) + 1;
 
-        let discords = match self.algo {
+        let discords = match self.resolve_algo(candidate_amnt) {
             Algorithm::Bruteforce => {
                 if self.dim_reduce > 1 {
                     anomaly_internal::brute_force_top_n(
                         &paa(&use_subslice.to_vec(), self.dim_reduce),
                         self.discord_size,
-                        discord_amnt
+                        discord_amnt,
+                        &self.metric
                     ).into_iter().map(|(dist, loc)| (dist, loc*((1000/self.dim_reduce) as usize))).collect()
                 } else {
                     anomaly_internal::brute_force_top_n(
                         &use_subslice,
                         self.discord_size,
-                        discord_amnt
+                        discord_amnt,
+                        &self.metric
                     )
                 }
             },
@@ -169,7 +336,10 @@ impl<'a, N: Float> Anomaly<'a, N> {
                     self.discord_size,
                     self.sax_word_length,
                     self.alpha,
-                    discord_amnt
+                    discord_amnt,
+                    self.numerosity_reduction,
+                    &self.fuzzy_match,
+                    &self.metric
                 )
             },
             Algorithm::Squeezer(threshold) => {
@@ -179,31 +349,80 @@ impl<'a, N: Float> Anomaly<'a, N> {
                     self.sax_word_length,
                     self.alpha,
                     threshold,
-                    discord_amnt
+                    discord_amnt,
+                    self.numerosity_reduction,
+                    &self.metric
                 )
-            }
+            },
+            Algorithm::HnswApprox { ef, m } => {
+                anomaly_internal::hnsw_top_n(
+                    &use_subslice,
+                    self.discord_size,
+                    ef,
+                    m,
+                    discord_amnt,
+                    &self.metric
+                )
+            },
+            Algorithm::VpTree => {
+                anomaly_internal::vptree_top_n(
+                    &use_subslice,
+                    self.discord_size,
+                    discord_amnt,
+                    &self.metric
+                )
+            },
+            Algorithm::Gsdmm { k, alpha, beta, maxit } => {
+                anomaly_internal::gsdmm_top_n(
+                    &use_subslice,
+                    self.discord_size,
+                    self.sax_word_length,
+                    self.alpha,
+                    k,
+                    alpha,
+                    beta,
+                    maxit,
+                    discord_amnt,
+                    self.numerosity_reduction,
+                    &self.metric
+                )
+            },
+            Algorithm::Auto { .. } => unreachable!("resolve_algo never returns Algorithm::Auto")
         };
 
         discords.into_iter().map(|(dist, loc)| (dist, loc+self.index.0)).collect()
     }
 
+    /// Finds the `k` most anomalous, mutually non-overlapping subsequences, ordered by discord
+    /// distance descending. A location within `discord_size` of one already returned is
+    /// rejected, so the results never overlap.
+    ///
+    /// A shortcut to `find_n_largest_discords`, which already maintains its candidates through
+    /// a bounded, sorted buffer (see `TopKDiscords`) with this exact non-overlap invariant.
+    pub fn k_discords(&self, k: usize) -> Vec<(f64, usize)> {
+        self.find_n_largest_discords(k)
+    }
+
     /// Finds all discords with a measured distance above `min_dist`.
     pub fn find_discords_min_dist(&self, min_dist: f64) -> Vec<(f64, usize)> {
         let use_subslice = self.data.get(self.index.0..self.index.1).unwrap();
+        let candidate_amnt = use_subslice.len().saturating_sub(self.discord_size) + 1;
 
-        let discords = match self.algo {
+        let discords = match self.resolve_algo(candidate_amnt) {
             Algorithm::Bruteforce => {
                 if self.dim_reduce > 1 {
                     anomaly_internal::brute_force_min_dist(
                         &paa(&use_subslice.to_vec(), self.dim_reduce),
                         self.discord_size,
-                        min_dist
+                        min_dist,
+                        &self.metric
                     ).into_iter().map(|(dist, loc)| (dist, loc*((1000/self.dim_reduce) as usize))).collect()
                 } else {
                     anomaly_internal::brute_force_min_dist(
                         &use_subslice,
                         self.discord_size,
-                        min_dist
+                        min_dist,
+                        &self.metric
                     )
                 }
             },
@@ -213,7 +432,10 @@ impl<'a, N: Float> Anomaly<'a, N> {
                     self.discord_size,
                     self.sax_word_length,
                     self.alpha,
-                    min_dist
+                    min_dist,
+                    self.numerosity_reduction,
+                    &self.fuzzy_match,
+                    &self.metric
                 )
             },
             Algorithm::Squeezer(threshold) => {
@@ -223,9 +445,45 @@ impl<'a, N: Float> Anomaly<'a, N> {
                     self.sax_word_length,
                     self.alpha,
                     threshold,
-                    min_dist
+                    min_dist,
+                    self.numerosity_reduction,
+                    &self.metric
                 )
-            }
+            },
+            Algorithm::HnswApprox { ef, m } => {
+                anomaly_internal::hnsw_min_dist(
+                    &use_subslice,
+                    self.discord_size,
+                    ef,
+                    m,
+                    min_dist,
+                    &self.metric
+                )
+            },
+            Algorithm::VpTree => {
+                anomaly_internal::vptree_min_dist(
+                    &use_subslice,
+                    self.discord_size,
+                    min_dist,
+                    &self.metric
+                )
+            },
+            Algorithm::Gsdmm { k, alpha, beta, maxit } => {
+                anomaly_internal::gsdmm_min_dist(
+                    &use_subslice,
+                    self.discord_size,
+                    self.sax_word_length,
+                    self.alpha,
+                    k,
+                    alpha,
+                    beta,
+                    maxit,
+                    min_dist,
+                    self.numerosity_reduction,
+                    &self.metric
+                )
+            },
+            Algorithm::Auto { .. } => unreachable!("resolve_algo never returns Algorithm::Auto")
         };
 
         discords.into_iter().map(|(dist, loc)| (dist, loc+self.index.0)).collect()
@@ -239,50 +497,56 @@ mod anomaly_internal {
     use std::ops::Deref;
     use crate::anomaly::{keogh_util, inner_algo};
     use crate::znorm;
+    use crate::util::{RangeSet, TopKDiscords};
+    use crate::dist::Proximity;
 
-    pub fn brute_force_top_n<N, R>(
+    pub fn brute_force_top_n<N, R, M>(
         data: &R,
         discord_size: usize,
-        discord_amnt: usize
-    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]> {
-        let mut discords = Vec::new();
-        let mut skip_over = Vec::new();
+        discord_amnt: usize,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        let mut discords = TopKDiscords::new(discord_amnt, discord_size);
+        let mut skip_over = RangeSet::new();
 
         loop {
             let discord = inner_algo::brute_force_internal(
                 data,
                 discord_size,
-                &skip_over
+                &skip_over,
+                metric
             );
 
             if discord.0 == 0.0 {
-                break discords
+                break discords.into_vec()
             }
 
-            discords.push(discord);
+            discords.try_insert(discord.0, discord.1);
 
             if discords.len() >= discord_amnt {
-                break discords
+                break discords.into_vec()
             }
 
             let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
-            skip_over.extend(min..discord.1+discord_size);
+            skip_over.insert_range(min, discord.1+discord_size);
         }
     }
 
-    pub fn brute_force_min_dist<N, R>(
+    pub fn brute_force_min_dist<N, R, M>(
         data: &R,
         discord_size: usize,
         min_dist: f64,
-    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]> {
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
         let mut discords = Vec::new();
-        let mut skip_over = Vec::new();
+        let mut skip_over = RangeSet::new();
 
         loop {
             let discord = inner_algo::brute_force_internal(
                 data,
                 discord_size,
-                &[]
+                &skip_over,
+                metric
             );
 
             if (discord.0 == 0.0) | (discord.0 < min_dist) {
@@ -292,16 +556,17 @@ mod anomaly_internal {
             discords.push(discord);
 
             let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
-            skip_over.extend(min..discord.1+discord_size);
+            skip_over.insert_range(min, discord.1+discord_size);
         }
     }
 
     #[inline]
-    pub fn brute_force_best<N, R>(
+    pub fn brute_force_best<N, R, M>(
         data: &R,
-        discord_size: usize
-    ) -> Option<(f64, usize)> where N: Float, R: Deref<Target=[N]> {
-        brute_force_top_n(data, discord_size, 1).pop()
+        discord_size: usize,
+        metric: &M
+    ) -> Option<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        brute_force_top_n(data, discord_size, 1, metric).pop()
     }
 
     /// The HOT SAX algorithm as proposed by Keogh et al.
@@ -310,23 +575,26 @@ mod anomaly_internal {
     ///
     /// ## Panics
     /// - `sax_word_length` is larger than `discord size`.
-    /// - `alpha` is under 3 or over 7.
+    /// - `alpha` is under 2.
     ///
     /// ## Returns
     /// A list of the distances of the top n discords (0), as well as their locations. (1)
     /// This list can have less elements if less discords were found.
-    pub fn hotsax_top_n<N, R>(
+    pub fn hotsax_top_n<N, R, M>(
         data: &R,
         discord_size: usize,
         sax_word_length: usize,
         alpha: usize,
-        discord_amnt: usize
-    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]> {
+        discord_amnt: usize,
+        numerosity_reduction: bool,
+        fuzzy_match: &Option<(f64, Vec<usize>)>,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
         let words = keogh_util::get_sax_words(data, discord_size, sax_word_length, alpha);
-        let (word_table, trie, znorm) = keogh_util::extract_hotsax_items(data, &words);
+        let (word_table, trie, znorm) = keogh_util::extract_hotsax_items(data, &words, numerosity_reduction, fuzzy_match);
 
-        let mut discords : Vec<(f64, usize)> = Vec::new();
-        let mut skip_over = Vec::new();
+        let mut discords = TopKDiscords::new(discord_amnt, discord_size);
+        let mut skip_over = RangeSet::new();
 
         loop {
             let discord = inner_algo::hot_sax_internal(
@@ -334,36 +602,40 @@ mod anomaly_internal {
                 &trie,
                 discord_size,
                 &znorm,
-                &skip_over
+                &skip_over,
+                metric
             );
 
             if discord.0 == 0.0 {
-                break discords
+                break discords.into_vec()
             }
 
-            discords.push(discord);
+            discords.try_insert(discord.0, discord.1);
 
             if discords.len() >= discord_amnt {
-                break discords
+                break discords.into_vec()
             }
 
             let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
-            skip_over.extend(min..discord.1+discord_size);
+            skip_over.insert_range(min, discord.1+discord_size);
         }
     }
 
-    pub fn hotsax_min_dist<N, R>(
+    pub fn hotsax_min_dist<N, R, M>(
         data: &R,
         discord_size: usize,
         sax_word_length: usize,
         alpha: usize,
         min_dist: f64,
-    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]> {
+        numerosity_reduction: bool,
+        fuzzy_match: &Option<(f64, Vec<usize>)>,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
         let words = keogh_util::get_sax_words(data, discord_size, sax_word_length, alpha);
-        let (word_table, trie, znorm) = keogh_util::extract_hotsax_items(data, &words);
+        let (word_table, trie, znorm) = keogh_util::extract_hotsax_items(data, &words, numerosity_reduction, fuzzy_match);
 
         let mut discords : Vec<(f64, usize)> = Vec::new();
-        let mut skip_over = Vec::new();
+        let mut skip_over = RangeSet::new();
 
         loop {
             let discord = inner_algo::hot_sax_internal(
@@ -371,7 +643,8 @@ mod anomaly_internal {
                 &trie,
                 discord_size,
                 &znorm,
-                &skip_over
+                &skip_over,
+                metric
             );
 
             if (discord.0 == 0.0) | (discord.0 < min_dist) {
@@ -381,7 +654,7 @@ mod anomaly_internal {
             discords.push(discord);
 
             let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
-            skip_over.extend(min..discord.1+discord_size);
+            skip_over.insert_range(min, discord.1+discord_size);
         }
     }
 
@@ -400,13 +673,16 @@ mod anomaly_internal {
     /// The distance of the best discord (0), as well as its location. (1)
     ///
     /// If such a discord isn't found, this function returns `None`.
-    pub fn hotsax_best<N, R>(
+    pub fn hotsax_best<N, R, M>(
         data: &R,
         discord_size: usize,
         sax_word_length: usize,
-        alpha: usize
-    ) -> Option<(f64, usize)> where N: Float, R: Deref<Target=[N]> {
-        hotsax_top_n(data, discord_size, sax_word_length, alpha, 1).pop()
+        alpha: usize,
+        numerosity_reduction: bool,
+        fuzzy_match: &Option<(f64, Vec<usize>)>,
+        metric: &M
+    ) -> Option<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        hotsax_top_n(data, discord_size, sax_word_length, alpha, 1, numerosity_reduction, fuzzy_match, metric).pop()
     }
 
     /// The HS-Squeezer algorithm.
@@ -415,24 +691,26 @@ mod anomaly_internal {
     ///
     /// ## Panics
     /// - `sax_word_length` is larger than `discord size`.
-    /// - `alpha` is under 3 or over 7.
+    /// - `alpha` is under 2.
     ///
     /// ## Returns
     /// A list of the distances of the top n discords (0), as well as their locations. (1)
     /// This list can have less elements if less discords were found.
-    pub fn hs_squeezer_top_n<N, R>(
+    pub fn hs_squeezer_top_n<N, R, M>(
         data: &R,
         discord_size: usize,
         sax_word_length: usize,
         alpha: usize,
         threshold: f64,
-        discord_amnt: usize
-    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]> {
+        discord_amnt: usize,
+        numerosity_reduction: bool,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
         let words = keogh_util::get_sax_words(data, discord_size, sax_word_length, alpha);
         let znorm = znorm(data);
 
-        let mut discords : Vec<(f64, usize)> = Vec::new();
-        let mut skip_over = Vec::new();
+        let mut discords = TopKDiscords::new(discord_amnt, discord_size);
+        let mut skip_over = RangeSet::new();
 
         loop {
             let discord = inner_algo::hs_squeezer_internal(
@@ -440,21 +718,23 @@ mod anomaly_internal {
                 discord_size,
                 &znorm,
                 threshold,
-                &skip_over
+                numerosity_reduction,
+                &skip_over,
+                metric
             );
 
             if discord.0 == 0.0 {
-                break discords
+                break discords.into_vec()
             }
 
-            discords.push(discord);
+            discords.try_insert(discord.0, discord.1);
 
             if discords.len() >= discord_amnt {
-                break discords
+                break discords.into_vec()
             }
 
             let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
-            skip_over.extend(min..discord.1+discord_size);
+            skip_over.insert_range(min, discord.1+discord_size);
         }
     }
 
@@ -464,24 +744,26 @@ mod anomaly_internal {
     ///
     /// ## Panics
     /// - `sax_word_length` is larger than `discord size`.
-    /// - `alpha` is under 3 or over 7.
+    /// - `alpha` is under 2.
     ///
     /// ## Returns
     /// A list of the distances of all discords above the min_dist (0), as well as their locations. (1)
     /// This list can have less elements if less discords were found.
-    pub fn hs_squeezer_min_dist<N, R>(
+    pub fn hs_squeezer_min_dist<N, R, M>(
         data: &R,
         discord_size: usize,
         sax_word_length: usize,
         alpha: usize,
         threshold: f64,
         min_dist: f64,
-    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]> {
+        numerosity_reduction: bool,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
         let words = keogh_util::get_sax_words(data, discord_size, sax_word_length, alpha);
         let znorm = znorm(data);
 
         let mut discords : Vec<(f64, usize)> = Vec::new();
-        let mut skip_over = Vec::new();
+        let mut skip_over = RangeSet::new();
 
         loop {
             let discord = inner_algo::hs_squeezer_internal(
@@ -489,7 +771,9 @@ mod anomaly_internal {
                 discord_size,
                 &znorm,
                  threshold,
-                &skip_over
+                numerosity_reduction,
+                &skip_over,
+                metric
             );
 
             if (discord.0 == 0.0) | (discord.0 < min_dist) {
@@ -499,7 +783,7 @@ mod anomaly_internal {
             discords.push(discord);
 
             let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
-            skip_over.extend(min..discord.1+discord_size);
+            skip_over.insert_range(min, discord.1+discord_size);
         }
     }
 
@@ -518,14 +802,303 @@ mod anomaly_internal {
     /// The distance of the best discord (0), as well as its location. (1)
     ///
     /// If such a discord isn't found, this function returns `None`.
-    pub fn hs_squeezer_best<N, R>(
+    pub fn hs_squeezer_best<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        sax_word_length: usize,
+        alpha: usize,
+        threshold: f64,
+        numerosity_reduction: bool,
+        metric: &M
+    ) -> Option<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        hs_squeezer_top_n(data, discord_size, sax_word_length, alpha, threshold, 1, numerosity_reduction, metric).pop()
+    }
+
+    /// Approximate discord discovery backed by an HNSW graph over the z-normalized
+    /// subsequences. Faster than `hotsax_top_n` on long series, at the cost of approximate
+    /// nearest-neighbor distances.
+    pub fn hnsw_top_n<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        ef: usize,
+        m: usize,
+        discord_amnt: usize,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> + Clone {
+        let index = keogh_util::build_hnsw_index(data, discord_size, m, ef, metric.clone());
+
+        let mut discords = TopKDiscords::new(discord_amnt, discord_size);
+        let mut skip_over = RangeSet::new();
+
+        loop {
+            let discord = inner_algo::hnsw_internal(
+                &index,
+                data.len(),
+                discord_size,
+                ef,
+                &skip_over
+            );
+
+            if discord.0 == 0.0 {
+                break discords.into_vec()
+            }
+
+            discords.try_insert(discord.0, discord.1);
+
+            if discords.len() >= discord_amnt {
+                break discords.into_vec()
+            }
+
+            let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
+            skip_over.insert_range(min, discord.1+discord_size);
+        }
+    }
+
+    pub fn hnsw_min_dist<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        ef: usize,
+        m: usize,
+        min_dist: f64,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> + Clone {
+        let index = keogh_util::build_hnsw_index(data, discord_size, m, ef, metric.clone());
+
+        let mut discords : Vec<(f64, usize)> = Vec::new();
+        let mut skip_over = RangeSet::new();
+
+        loop {
+            let discord = inner_algo::hnsw_internal(
+                &index,
+                data.len(),
+                discord_size,
+                ef,
+                &skip_over
+            );
+
+            if (discord.0 == 0.0) | (discord.0 < min_dist) {
+                break discords
+            }
+
+            discords.push(discord);
+
+            let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
+            skip_over.insert_range(min, discord.1+discord_size);
+        }
+    }
+
+    #[inline]
+    pub fn hnsw_best<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        ef: usize,
+        m: usize,
+        metric: &M
+    ) -> Option<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> + Clone {
+        hnsw_top_n(data, discord_size, ef, m, 1, metric).pop()
+    }
+
+    /// Exact discord discovery backed by a vantage-point tree over the z-normalized
+    /// subsequences. Faster than `brute_force_top_n` on long series; the nearest-neighbor
+    /// distances are exact whenever `metric` obeys the triangle inequality.
+    pub fn vptree_top_n<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        discord_amnt: usize,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> + Clone {
+        let tree = keogh_util::build_vp_tree(data, discord_size, metric.clone());
+
+        let mut discords = TopKDiscords::new(discord_amnt, discord_size);
+        let mut skip_over = RangeSet::new();
+
+        loop {
+            let discord = inner_algo::vptree_internal(
+                &tree,
+                data.len(),
+                discord_size,
+                &skip_over
+            );
+
+            if discord.0 == 0.0 {
+                break discords.into_vec()
+            }
+
+            discords.try_insert(discord.0, discord.1);
+
+            if discords.len() >= discord_amnt {
+                break discords.into_vec()
+            }
+
+            let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
+            skip_over.insert_range(min, discord.1+discord_size);
+        }
+    }
+
+    pub fn vptree_min_dist<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        min_dist: f64,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> + Clone {
+        let tree = keogh_util::build_vp_tree(data, discord_size, metric.clone());
+
+        let mut discords : Vec<(f64, usize)> = Vec::new();
+        let mut skip_over = RangeSet::new();
+
+        loop {
+            let discord = inner_algo::vptree_internal(
+                &tree,
+                data.len(),
+                discord_size,
+                &skip_over
+            );
+
+            if (discord.0 == 0.0) | (discord.0 < min_dist) {
+                break discords
+            }
+
+            discords.push(discord);
+
+            let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
+            skip_over.insert_range(min, discord.1+discord_size);
+        }
+    }
+
+    #[inline]
+    pub fn vptree_best<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        metric: &M
+    ) -> Option<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> + Clone {
+        vptree_top_n(data, discord_size, 1, metric).pop()
+    }
+
+    /// Discord discovery over clusters grouped with the GSDMM model, as an alternative to
+    /// `hs_squeezer_top_n`'s `Squeezer`-backed clustering.
+    ///
+    /// ## Panics
+    /// - `sax_word_length` is larger than `discord size`.
+    /// - `alpha` is under 2.
+    ///
+    /// ## Returns
+    /// A list of the distances of the top n discords (0), as well as their locations. (1)
+    /// This list can have less elements if less discords were found.
+    pub fn gsdmm_top_n<N, R, M>(
         data: &R,
         discord_size: usize,
         sax_word_length: usize,
         alpha: usize,
-        threshold: f64
-    ) -> Option<(f64, usize)> where N: Float, R: Deref<Target=[N]> {
-        hs_squeezer_top_n(data, discord_size, sax_word_length, alpha, threshold, 1).pop()
+        k: usize,
+        gsdmm_alpha: f64,
+        beta: f64,
+        maxit: usize,
+        discord_amnt: usize,
+        numerosity_reduction: bool,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        let words = keogh_util::get_sax_words(data, discord_size, sax_word_length, alpha);
+        let znorm = znorm(data);
+
+        let mut discords = TopKDiscords::new(discord_amnt, discord_size);
+        let mut skip_over = RangeSet::new();
+
+        loop {
+            let discord = inner_algo::gsdmm_internal(
+                &words,
+                discord_size,
+                &znorm,
+                alpha,
+                k,
+                gsdmm_alpha,
+                beta,
+                maxit,
+                numerosity_reduction,
+                &skip_over,
+                metric
+            );
+
+            if discord.0 == 0.0 {
+                break discords.into_vec()
+            }
+
+            discords.try_insert(discord.0, discord.1);
+
+            if discords.len() >= discord_amnt {
+                break discords.into_vec()
+            }
+
+            let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
+            skip_over.insert_range(min, discord.1+discord_size);
+        }
+    }
+
+    /// ## Panics
+    /// - `sax_word_length` is larger than `discord size`.
+    /// - `alpha` is under 2.
+    ///
+    /// ## Returns
+    /// A list of the distances of all discords above the min_dist (0), as well as their locations. (1)
+    /// This list can have less elements if less discords were found.
+    pub fn gsdmm_min_dist<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        sax_word_length: usize,
+        alpha: usize,
+        k: usize,
+        gsdmm_alpha: f64,
+        beta: f64,
+        maxit: usize,
+        min_dist: f64,
+        numerosity_reduction: bool,
+        metric: &M
+    ) -> Vec<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        let words = keogh_util::get_sax_words(data, discord_size, sax_word_length, alpha);
+        let znorm = znorm(data);
+
+        let mut discords : Vec<(f64, usize)> = Vec::new();
+        let mut skip_over = RangeSet::new();
+
+        loop {
+            let discord = inner_algo::gsdmm_internal(
+                &words,
+                discord_size,
+                &znorm,
+                alpha,
+                k,
+                gsdmm_alpha,
+                beta,
+                maxit,
+                numerosity_reduction,
+                &skip_over,
+                metric
+            );
+
+            if (discord.0 == 0.0) | (discord.0 < min_dist) {
+                break discords
+            }
+
+            discords.push(discord);
+
+            let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
+            skip_over.insert_range(min, discord.1+discord_size);
+        }
+    }
+
+    #[inline]
+    pub fn gsdmm_best<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        sax_word_length: usize,
+        alpha: usize,
+        k: usize,
+        gsdmm_alpha: f64,
+        beta: f64,
+        maxit: usize,
+        numerosity_reduction: bool,
+        metric: &M
+    ) -> Option<(f64, usize)> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        gsdmm_top_n(data, discord_size, sax_word_length, alpha, k, gsdmm_alpha, beta, maxit, 1, numerosity_reduction, metric).pop()
     }
 }
 
@@ -535,28 +1108,39 @@ mod inner_algo {
     use num::Float;
     use std::ops::Deref;
     use std::collections::HashSet;
-    use crate::gaussian;
     use rand::seq::SliceRandom;
     use crate::squeezer::{Cluster, squeezer};
+    use crate::gsdmm::gsdmm;
+    use crate::util::RangeSet;
+    use crate::dist::Proximity;
+    use crate::anomaly::keogh_util;
 
     /// Brute force algorithm for finding discords. Made private due to substandard performance.
     ///
     /// Incredibly accurate, but slow to execute. Always takes n^2 time.
-    pub fn brute_force_internal<N, R>(
+    pub fn brute_force_internal<N, R, M>(
         data: &R,
         n: usize,
-        skip_over: &[usize]
-    ) -> (f64, usize) where N: Float, R: Deref<Target=[N]> {
+        skip_over: &RangeSet,
+        metric: &M
+    ) -> (f64, usize) where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
         let mut best_dist = 0.0;
         let mut best_loc = 0;
 
         for i in 0..data.len()-n+1 {
-            if skip_over.contains(&i) { continue }
+            if skip_over.contains(i) { continue }
             let mut neigh_dist = std::f64::INFINITY;
             for j in 0..data.len()-n+1 {
                 if (i as isize - j as isize).abs() >= n as isize {
-                    let dist = gaussian(&data[i..i+n-1], &data[j..j+n-1]);
-                    neigh_dist = neigh_dist.min(dist.to_f64().unwrap());
+                    // Bails out mid-computation once the partial sum already exceeds the
+                    // current running minimum, since such a `j` can't lower `neigh_dist` anyway.
+                    if let Some(dist) = metric.distance_early_abandon(
+                        &data[i..i+n-1],
+                        &data[j..j+n-1],
+                        N::from(neigh_dist).unwrap_or_else(N::infinity)
+                    ) {
+                        neigh_dist = neigh_dist.min(dist.to_f64().unwrap());
+                    }
                 }
             }
 
@@ -578,20 +1162,21 @@ mod inner_algo {
     /// - `discord_size` : The size of the discords to be found.
     /// - `znorm_data` : The data.
     /// - `skip_over` : A list of indexes to skip over.
-    pub fn hot_sax_internal<N, R>(
+    pub fn hot_sax_internal<N, R, M>(
         sorted_word_table: &Vec<(usize, (&String, usize))>,
         word_trie: &AugmentedTrie,
         discord_size: usize,
         data: &R,
-        skip_over: &[usize]
-    ) -> (f64, usize) where N: Float, R: Deref<Target=[N]> {
+        skip_over: &RangeSet,
+        metric: &M
+    ) -> (f64, usize) where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
         // The actual discord discovery.
         let mut best_dist = 0.0;
         let mut best_loc = 0;
 
         // Outer loop heuristic: Uses sorted word table.
         for (i,(word,_)) in sorted_word_table.into_iter() {
-            if skip_over.contains(i) {
+            if skip_over.contains(*i) {
                 continue
             }
 
@@ -607,12 +1192,21 @@ mod inner_algo {
             // Inner loop heuristic: Checks the occurrences of the same SAX word using the word trie.
             for j in occurrences.into_iter() {
                 if (*i as isize - j as isize).abs() >= discord_size as isize {
-                    // Retrieves the gaussian distance between to slices
-                    let dist = gaussian(&data[*i..*i+ discord_size -1], &data[j..j+ discord_size -1]).to_f64().unwrap();
-                    // Updates the neighbouring distance
-                    neigh_dist = neigh_dist.min(dist);
-                    // Stops searching if a distance word than `best_dist` was found
-                    if dist < best_dist { do_random_search = false; break;}
+                    // Bails out mid-computation once the partial sum already exceeds the
+                    // current `neigh_dist`, since such a `j` can't lower it (nor, being at
+                    // least `neigh_dist`, pass the `dist < best_dist` check below).
+                    match metric.distance_early_abandon(
+                        &data[*i..*i+ discord_size -1],
+                        &data[j..j+ discord_size -1],
+                        N::from(neigh_dist).unwrap_or_else(N::infinity)
+                    ) {
+                        Some(dist) => {
+                            let dist = dist.to_f64().unwrap();
+                            neigh_dist = neigh_dist.min(dist);
+                            if dist < best_dist { do_random_search = false; break; }
+                        },
+                        None => continue
+                    }
                 }
             }
 
@@ -626,9 +1220,18 @@ mod inner_algo {
             // Calculates the closest neighbouring distance
             for j in nums.into_iter() {
                 if (*i as isize - j as isize).abs() >= discord_size as isize {
-                    let dist = gaussian(&data[*i..*i + discord_size - 1], &data[j..j + discord_size - 1]).to_f64().unwrap();
-                    neigh_dist = neigh_dist.min(dist);
-                    if dist < best_dist { break; }
+                    match metric.distance_early_abandon(
+                        &data[*i..*i + discord_size - 1],
+                        &data[j..j + discord_size - 1],
+                        N::from(neigh_dist).unwrap_or_else(N::infinity)
+                    ) {
+                        Some(dist) => {
+                            let dist = dist.to_f64().unwrap();
+                            neigh_dist = neigh_dist.min(dist);
+                            if dist < best_dist { break; }
+                        },
+                        None => continue
+                    }
                 }
             }
 
@@ -642,26 +1245,43 @@ mod inner_algo {
         (best_dist, best_loc)
     }
 
-    pub fn hs_squeezer_internal<N, R>(
+    pub fn hs_squeezer_internal<N, R, M>(
         words: &Vec<String>,
         discord_size: usize,
         data: &R,
         threshold: f64,
-        skip_over: &[usize]
-    ) -> (f64, usize) where N: Float, R: Deref<Target=[N]> {
+        numerosity_reduction: bool,
+        skip_over: &RangeSet,
+        metric: &M
+    ) -> (f64, usize) where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
         // The actual discord discovery.
         let mut best_dist = 0.0;
         let mut best_loc = 0;
 
-        // Uses squeezer algorithm to get clusters.
-        let clusters = squeezer(&words, threshold);
+        // When enabled, collapses runs of identical consecutive words down to their first
+        // occurrence before clustering, so a long flat stretch contributes one candidate
+        // instead of one per window position.
+        let indexed_words: Vec<(usize, &String)> = if numerosity_reduction {
+            keogh_util::reduce_numerosity(words)
+        } else {
+            words.iter().enumerate().collect()
+        };
+
+        let reduced_words: Vec<String> = indexed_words.iter().map(|&(_, word)| word.clone()).collect();
 
-        let mut indexes = clusters.iter().min_by_key(|cluster| cluster.len()).unwrap().vec();
+        // Uses squeezer algorithm to get clusters, then maps its positions (into `reduced_words`)
+        // back to the original window indices.
+        let clusters: Vec<Cluster> = squeezer(&reduced_words, threshold)
+            .into_iter()
+            .map(|cluster| cluster.into_iter().map(|pos| indexed_words[pos].0).collect())
+            .collect();
+
+        let mut indexes = clusters.iter().min_by_key(|cluster| cluster.len()).unwrap().clone();
         indexes.append(&mut (0..data.len()).collect());
 
         // Outer loop heuristic: Uses sorted word table.
         for i in indexes.into_iter() {
-            if skip_over.contains(&i) {
+            if skip_over.contains(i) {
                 continue
             }
 
@@ -683,12 +1303,21 @@ mod inner_algo {
             // Inner loop heuristic: Checks the occurrences of the same SAX word using the word trie.
             for &j in curr_cluster.iter() {
                 if (i as isize - j as isize).abs() >= discord_size as isize {
-                    // Retrieves the gaussian distance between to slices
-                    let dist = gaussian(&data[i..i+ discord_size -1], &data[j..j+ discord_size -1]).to_f64().unwrap();
-                    // Updates the neighbouring distance
-                    neigh_dist = neigh_dist.min(dist);
-                    // Stops searching if a distance word than `best_dist` was found
-                    if dist < best_dist { do_random_search = false; break;}
+                    // Bails out mid-computation once the partial sum already exceeds the
+                    // current `neigh_dist`, since such a `j` can't lower it (nor, being at
+                    // least `neigh_dist`, pass the `dist < best_dist` check below).
+                    match metric.distance_early_abandon(
+                        &data[i..i+ discord_size -1],
+                        &data[j..j+ discord_size -1],
+                        N::from(neigh_dist).unwrap_or_else(N::infinity)
+                    ) {
+                        Some(dist) => {
+                            let dist = dist.to_f64().unwrap();
+                            neigh_dist = neigh_dist.min(dist);
+                            if dist < best_dist { do_random_search = false; break; }
+                        },
+                        None => continue
+                    }
                 }
             }
 
@@ -702,9 +1331,134 @@ mod inner_algo {
             // Calculates the closest neighbouring distance
             for j in nums.into_iter() {
                 if (i as isize - j as isize).abs() >= discord_size as isize {
-                    let dist = gaussian(&data[i..i + discord_size - 1], &data[j..j + discord_size - 1]).to_f64().unwrap();
-                    neigh_dist = neigh_dist.min(dist);
-                    if dist < best_dist { break; }
+                    match metric.distance_early_abandon(
+                        &data[i..i + discord_size - 1],
+                        &data[j..j + discord_size - 1],
+                        N::from(neigh_dist).unwrap_or_else(N::infinity)
+                    ) {
+                        Some(dist) => {
+                            let dist = dist.to_f64().unwrap();
+                            neigh_dist = neigh_dist.min(dist);
+                            if dist < best_dist { break; }
+                        },
+                        None => continue
+                    }
+                }
+            }
+
+            // Updates the best distance if the neighbouring distance is larger.
+            if (neigh_dist > best_dist) & (neigh_dist < std::f64::INFINITY) {
+                best_dist = neigh_dist;
+                best_loc = i;
+            }
+        }
+
+        (best_dist, best_loc)
+    }
+
+    /// Same outer/inner loop heuristic as `hs_squeezer_internal`, but the candidate clusters
+    /// come from `gsdmm` instead of `squeezer`.
+    pub fn gsdmm_internal<N, R, M>(
+        words: &Vec<String>,
+        discord_size: usize,
+        data: &R,
+        vocab_size: usize,
+        k: usize,
+        alpha: f64,
+        beta: f64,
+        maxit: usize,
+        numerosity_reduction: bool,
+        skip_over: &RangeSet,
+        metric: &M
+    ) -> (f64, usize) where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        // The actual discord discovery.
+        let mut best_dist = 0.0;
+        let mut best_loc = 0;
+
+        // When enabled, collapses runs of identical consecutive words down to their first
+        // occurrence before clustering, so a long flat stretch contributes one candidate
+        // instead of one per window position.
+        let indexed_words: Vec<(usize, &String)> = if numerosity_reduction {
+            keogh_util::reduce_numerosity(words)
+        } else {
+            words.iter().enumerate().collect()
+        };
+
+        let reduced_words: Vec<String> = indexed_words.iter().map(|&(_, word)| word.clone()).collect();
+
+        // Uses the GSDMM model to get clusters, then maps its positions (into `reduced_words`)
+        // back to the original window indices.
+        let clusters: Vec<Cluster> = gsdmm(&reduced_words, vocab_size, k, alpha, beta, maxit)
+            .into_iter()
+            .map(|cluster| cluster.into_iter().map(|pos| indexed_words[pos].0).collect())
+            .collect();
+
+        let mut indexes = clusters.iter().min_by_key(|cluster| cluster.len()).unwrap().clone();
+        indexes.append(&mut (0..data.len()).collect());
+
+        // Outer loop heuristic: Uses sorted word table.
+        for i in indexes.into_iter() {
+            if skip_over.contains(i) {
+                continue
+            }
+
+            // Boolean that checks whether to perform the random search
+            let mut do_random_search = true;
+
+            // The neighbouring distance for the inner loop
+            let mut neigh_dist = std::f64::INFINITY;
+
+            // Finds the cluster that the current item is in.
+            let curr_cluster = if let Some(cluster) = clusters
+                .iter()
+                .find(|cluster| cluster.contains(&i)) {
+                cluster
+            } else {
+                continue;
+            };
+
+            // Inner loop heuristic: Checks the occurrences of the same SAX word using the word trie.
+            for &j in curr_cluster.iter() {
+                if (i as isize - j as isize).abs() >= discord_size as isize {
+                    // Bails out mid-computation once the partial sum already exceeds the
+                    // current `neigh_dist`, since such a `j` can't lower it (nor, being at
+                    // least `neigh_dist`, pass the `dist < best_dist` check below).
+                    match metric.distance_early_abandon(
+                        &data[i..i+ discord_size -1],
+                        &data[j..j+ discord_size -1],
+                        N::from(neigh_dist).unwrap_or_else(N::infinity)
+                    ) {
+                        Some(dist) => {
+                            let dist = dist.to_f64().unwrap();
+                            neigh_dist = neigh_dist.min(dist);
+                            if dist < best_dist { do_random_search = false; break; }
+                        },
+                        None => continue
+                    }
+                }
+            }
+
+            if !do_random_search { continue }
+
+            // Gets all indexes and shuffles them
+            let mut nums: Vec<usize> = (0..data.len()- discord_size +1).collect();
+            nums.shuffle(&mut rand::thread_rng());
+
+            // Calculates the closest neighbouring distance
+            for j in nums.into_iter() {
+                if (i as isize - j as isize).abs() >= discord_size as isize {
+                    match metric.distance_early_abandon(
+                        &data[i..i + discord_size - 1],
+                        &data[j..j + discord_size - 1],
+                        N::from(neigh_dist).unwrap_or_else(N::infinity)
+                    ) {
+                        Some(dist) => {
+                            let dist = dist.to_f64().unwrap();
+                            neigh_dist = neigh_dist.min(dist);
+                            if dist < best_dist { break; }
+                        },
+                        None => continue
+                    }
                 }
             }
 
@@ -717,6 +1471,59 @@ mod inner_algo {
 
         (best_dist, best_loc)
     }
+
+    /// Queries the HNSW graph for each candidate window's approximate nearest-neighbor
+    /// distance, keeping the largest one found. `skip_over` excludes windows already claimed
+    /// by a previously returned discord.
+    pub fn hnsw_internal<N: Float, M: Proximity<N>>(
+        index: &crate::hnsw::HnswIndex<N, M>,
+        data_len: usize,
+        discord_size: usize,
+        ef: usize,
+        skip_over: &RangeSet
+    ) -> (f64, usize) {
+        let mut best_dist = 0.0;
+        let mut best_loc = 0;
+
+        for i in 0..data_len-discord_size+1 {
+            if skip_over.contains(i) { continue }
+
+            if let Some((dist, _)) = index.query_nearest(i, ef, discord_size) {
+                if dist > best_dist {
+                    best_dist = dist;
+                    best_loc = i;
+                }
+            }
+        }
+
+        (best_dist, best_loc)
+    }
+
+    /// Queries the VP-tree for each candidate window's exact nearest-neighbor distance,
+    /// keeping the largest one found. `skip_over` excludes windows already claimed by a
+    /// previously returned discord.
+    pub fn vptree_internal<N: Float, M: Proximity<N>>(
+        tree: &crate::vp_tree::VpTree<N, M>,
+        data_len: usize,
+        discord_size: usize,
+        skip_over: &RangeSet
+    ) -> (f64, usize) {
+        let mut best_dist = 0.0;
+        let mut best_loc = 0;
+
+        for i in 0..data_len-discord_size+1 {
+            if skip_over.contains(i) { continue }
+
+            if let Some((dist, _)) = tree.nearest_neighbor(i, discord_size) {
+                if dist > best_dist {
+                    best_dist = dist;
+                    best_loc = i;
+                }
+            }
+        }
+
+        (best_dist, best_loc)
+    }
 }
 
 // Utilities used by algorithms, for generating certain parameters.
@@ -727,40 +1534,89 @@ mod keogh_util {
     use std::collections::HashMap;
     use crate::znorm;
     use rand::seq::SliceRandom;
+    use crate::dist::Proximity;
 
-    pub fn attach_freq_sax_words(words: &Vec<String>) -> Vec<(&String, usize)> {
+    pub fn attach_freq_sax_words<'a>(indexed_words: &[(usize, &'a String)]) -> Vec<(usize, (&'a String, usize))> {
         let mut freqmap: HashMap<&String, usize> = HashMap::new();
 
-        words.iter().for_each(|word| {
-            if freqmap.contains_key(word) {
-                freqmap.get_mut(word).unwrap().add_assign(1);
+        indexed_words.iter().for_each(|(_, word)| {
+            if freqmap.contains_key(*word) {
+                freqmap.get_mut(*word).unwrap().add_assign(1);
             }
             else {
-                freqmap.insert(word, 1);
+                freqmap.insert(*word, 1);
             }
         });
 
-        words.iter().map(|word| {
-            (word, freqmap[word])
+        indexed_words.iter().map(|&(i, word)| {
+            (i, (word, freqmap[word]))
+        }).collect()
+    }
+
+    /// Like `attach_freq_sax_words`, but a word's "frequency" is the size of the fuzzy
+    /// cluster (see `crate::fuzzy_match::fuzzy_cluster`) it falls into at the given
+    /// `threshold`/`ngram_sizes`, rather than its exact-match count.
+    pub fn fuzzy_freq_sax_words<'a>(
+        indexed_words: &[(usize, &'a String)],
+        threshold: f64,
+        ngram_sizes: &[usize],
+    ) -> Vec<(usize, (&'a String, usize))> {
+        let words: Vec<String> = indexed_words.iter().map(|&(_, word)| word.clone()).collect();
+        let clusters = crate::fuzzy_match::fuzzy_cluster(&words, ngram_sizes, threshold);
+
+        let mut cluster_size_of = vec![0; words.len()];
+        for cluster in &clusters {
+            for &pos in cluster {
+                cluster_size_of[pos] = cluster.len();
+            }
+        }
+
+        indexed_words.iter().enumerate().map(|(pos, &(i, word))| {
+            (i, (word, cluster_size_of[pos]))
         }).collect()
     }
 
+    /// Collapses consecutive windows that map to the identical SAX word, keeping only the
+    /// first occurrence's index. Long runs of self-similar subsequences otherwise dominate the
+    /// frequency table and bias the outer-loop ordering in `extract_hotsax_items`.
+    pub fn reduce_numerosity<'a>(words: &'a Vec<String>) -> Vec<(usize, &'a String)> {
+        let mut reduced = Vec::new();
+        let mut last: Option<&String> = None;
+
+        for (i, word) in words.iter().enumerate() {
+            if last != Some(word) {
+                reduced.push((i, word));
+                last = Some(word);
+            }
+        }
+
+        reduced
+    }
+
     pub fn extract_hotsax_items<'a, N, R>(
         data: &R,
-        words: &'a Vec<String>
+        words: &'a Vec<String>,
+        numerosity_reduction: bool,
+        fuzzy_match: &Option<(f64, Vec<usize>)>,
     ) -> (Vec<(usize, (&'a String, usize))>, AugmentedTrie, Vec<N>) where N: Float, R: Deref<Target=[N]> {
         let znorm = znorm(data);
 
-        let trie = AugmentedTrie::from_words(words.iter().enumerate().collect());
+        let indexed_words: Vec<(usize, &String)> = if numerosity_reduction {
+            reduce_numerosity(words)
+        } else {
+            words.iter().enumerate().collect()
+        };
+
+        let trie = AugmentedTrie::from_words(indexed_words.clone());
 
         // Contains (index, (SAXword, frequency))
         // The former is useful to iterate over the data in an ordered way.
         // The latter is useful for the magic inner loop.
         // `word_table`
-        let word_table = attach_freq_sax_words(&words)
-            .into_iter()
-            .enumerate()
-            .collect::<Vec<(usize, (&String, usize))>>();
+        let word_table = match fuzzy_match {
+            Some((threshold, ngram_sizes)) => fuzzy_freq_sax_words(&indexed_words, *threshold, ngram_sizes),
+            None => attach_freq_sax_words(&indexed_words),
+        };
 
         // Gets the minimum frequency from the word table
         let min_freq = (word_table.iter().min_by_key(|elem| (elem.1).1).unwrap().1).1;
@@ -791,4 +1647,57 @@ mod keogh_util {
 
         words
     }
+
+    /// Builds an HNSW index over every length-`discord_size` window of `data`, each inserted
+    /// as its own z-normalized vector, compared using `metric`.
+    pub fn build_hnsw_index<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        m: usize,
+        ef_construction: usize,
+        metric: M,
+    ) -> crate::hnsw::HnswIndex<N, M> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        let mut index = crate::hnsw::HnswIndex::new(m, ef_construction, metric);
+
+        for i in 0..data.len()-discord_size+1 {
+            let window = znorm(&data[i..i+discord_size].to_vec());
+            index.insert(i, window, discord_size);
+        }
+
+        index
+    }
+
+    /// Builds a VP-tree over every length-`discord_size` window of `data`, each inserted as
+    /// its own z-normalized vector, compared using `metric`.
+    pub fn build_vp_tree<N, R, M>(
+        data: &R,
+        discord_size: usize,
+        metric: M,
+    ) -> crate::vp_tree::VpTree<N, M> where N: Float, R: Deref<Target=[N]>, M: Proximity<N> {
+        let points = (0..data.len()-discord_size+1)
+            .map(|i| (i, znorm(&data[i..i+discord_size].to_vec())))
+            .collect();
+
+        crate::vp_tree::VpTree::build(points, metric)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dist::Euclidean;
+
+    #[test]
+    fn brute_force_min_dist_terminates_and_skips_found_discords() {
+        // A near-constant series with a single, unmistakable spike. If the exclusion zone
+        // built up across iterations isn't actually consulted, the loop re-finds the same
+        // discord forever instead of running out of candidates above min_dist.
+        let mut data = vec![0.0; 100];
+        for v in data.iter_mut().skip(50).take(5) {
+            *v = 10.0;
+        }
+
+        let discords = super::anomaly_internal::brute_force_min_dist(&data, 10, 0.1, &Euclidean);
+
+        assert!(!discords.is_empty());
+    }
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 use num::Float;
 use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::Mutex;
 
 lazy_static!(
     static ref BREAKPOINTS: [Vec<f64>; 5] = [
@@ -9,8 +11,67 @@ lazy_static!(
         vec![-0.97, -0.43, 0.0, 0.43, 0.97], // 6
         vec![-1.07, -0.57, -0.18, 0.18, 0.57, 1.07] // 7
     ];
+
+    /// Breakpoints for alphabet sizes outside `BREAKPOINTS`'s hardcoded `3..=7` range,
+    /// computed on first use by `gaussian_breakpoints` and kept here since every `sax` call
+    /// with the same `alpha` would otherwise recompute them from scratch.
+    static ref BREAKPOINT_CACHE: Mutex<HashMap<usize, Vec<f64>>> = Mutex::new(HashMap::new());
 );
 
+/// Approximates the inverse error function using Winitzki's rational approximation, accurate
+/// to within about `1.3e-4` over `x in (-1, 1)`.
+fn erfinv(x: f64) -> f64 {
+    const A: f64 = 0.147;
+
+    let ln_term = (1.0 - x*x).ln();
+    let p = 2.0 / (std::f64::consts::PI * A) + ln_term / 2.0;
+
+    x.signum() * ((p*p - ln_term/A).sqrt() - p).sqrt()
+}
+
+/// The largest alphabet size whose symbols `to_sax_letter` can still encode as a single
+/// byte-valued `char`: rank `alpha - 1` is added onto `'a' as u8` (`97`), and `97 + 158 == 255`
+/// is the last value that fits in a `u8` without overflowing. Ranks past 30 land beyond ASCII.
+const MAX_ALPHA: usize = 159;
+
+/// Computes the `alpha - 1` equiprobable Gaussian breakpoints that split the standard normal
+/// distribution into `alpha` equal-probability bins. Breakpoint `i` (`i = 1..alpha`) is the
+/// `i/alpha` quantile of the standard normal, `z_i = sqrt(2) * erfinv(2*i/alpha - 1)`.
+///
+/// ## Panics
+/// - `alpha` is under 2.
+/// - `alpha` is over [`MAX_ALPHA`], since `to_sax_letter` encodes each symbol as a single
+///   byte-valued `char` and can't represent a rank that high without overflowing.
+fn gaussian_breakpoints(alpha: usize) -> Vec<f64> {
+    if alpha < 2 {
+        panic!("Invalid setting for alphabet size ({}). At least 2 is required.", alpha);
+    }
+    if alpha > MAX_ALPHA {
+        panic!(
+            "Invalid setting for alphabet size ({}). At most {} is supported, since symbols are encoded as a single byte ('a' + rank).",
+            alpha, MAX_ALPHA
+        );
+    }
+
+    (1..alpha)
+        .map(|i| 2.0_f64.sqrt() * erfinv(2.0 * i as f64 / alpha as f64 - 1.0))
+        .collect()
+}
+
+/// Returns the breakpoints for `alpha`, using the hardcoded `BREAKPOINTS` table as a fast
+/// path (for exact parity with the paper's published values) when `alpha` is `3..=7`, and
+/// `gaussian_breakpoints` cached in `BREAKPOINT_CACHE` otherwise.
+fn breakpoints_for(alpha: usize) -> Vec<f64> {
+    if (3..=7).contains(&alpha) {
+        return BREAKPOINTS[alpha-3].clone();
+    }
+
+    BREAKPOINT_CACHE.lock().unwrap()
+        .entry(alpha)
+        .or_insert_with(|| gaussian_breakpoints(alpha))
+        .clone()
+}
+
 /// Returns a piecewise approximation of the original list of values.
 /// The size of the output array will be the same as `dim`.
 pub fn paa<N>(data: &Vec<N>, dim: usize) -> Vec<N> where N: Float {
@@ -37,7 +98,7 @@ pub fn paa<N>(data: &Vec<N>, dim: usize) -> Vec<N> where N: Float {
 }
 
 fn to_sax_letter<N>(elem: &N, alpha: usize) -> char where N: Float {
-    let breakpoints = &BREAKPOINTS[alpha-3];
+    let breakpoints = breakpoints_for(alpha);
 
     let num = elem.to_f64().unwrap();
 
@@ -53,10 +114,12 @@ fn to_sax_letter<N>(elem: &N, alpha: usize) -> char where N: Float {
 /// Returns a sax word representation of the original list.
 ///
 /// `word_size` determines the length of the word, and `alpha` represents the alphabet size.
+/// `alpha` in `3..=7` uses the paper's hardcoded breakpoints; any other `alpha` from 2 up to
+/// [`MAX_ALPHA`] falls back to breakpoints computed (and cached) from the Gaussian quantile
+/// function.
 ///
 /// # Panics
-/// - if `alpha` is not between 3 and 7. Higher numbers can only be supported if the static
-/// variable `BREAKPOINTS` is updated.
+/// - if `alpha` is under 2 or over [`MAX_ALPHA`].
 pub fn sax<N>(data: &Vec<N>, word_size: usize, alpha: usize) -> String where N: Float {
     let norm = super::util::znorm(data);
     let paa = paa(&norm, word_size);
@@ -67,4 +130,81 @@ pub fn sax<N>(data: &Vec<N>, word_size: usize, alpha: usize) -> String where N:
         .collect();
 
     return string;
+}
+
+/// The SAX "MINDIST" lower bound on the Euclidean distance between the (unknown) original
+/// z-normalized subsequences that `word_q` and `word_c` were derived from, as defined by Keogh
+/// et al.
+///
+/// Per-symbol, `cell(r, s)` is `0` once the symbols' ranks `r`/`s` are adjacent or equal (since
+/// adjacent SAX regions touch, so nothing can be said about the distance), otherwise the gap
+/// between the two regions' nearest breakpoints. The total is then scaled by
+/// `sqrt(original_len / word_len)` to account for PAA's averaging. Since this never overestimates
+/// the true distance, it can be used to discard a candidate (or an entire trie bucket sharing its
+/// word) the moment it exceeds the best discord distance found so far, without ever touching the
+/// original subsequence data.
+///
+/// `alpha` in `3..=7` uses the paper's hardcoded breakpoints; any other `alpha` from 2 up to
+/// [`MAX_ALPHA`] falls back to the same cached Gaussian-quantile breakpoints `sax` uses.
+///
+/// # Panics
+/// - if `word_q` and `word_c` differ in length.
+pub fn mindist(word_q: &str, word_c: &str, original_len: usize, alpha: usize) -> f64 {
+    let word_len = word_q.chars().count();
+    assert_eq!(word_len, word_c.chars().count(), "word_q and word_c must be the same length");
+
+    let breakpoints = breakpoints_for(alpha);
+
+    let sum_sq: f64 = word_q.chars().zip(word_c.chars())
+        .map(|(q, c)| {
+            let r = (q as u8 - b'a') as usize;
+            let s = (c as u8 - b'a') as usize;
+
+            let cell = if (r as isize - s as isize).abs() <= 1 {
+                0.0
+            } else {
+                let (hi, lo) = (r.max(s), r.min(s));
+                breakpoints[hi - 1] - breakpoints[lo]
+            };
+
+            cell * cell
+        })
+        .sum();
+
+    (original_len as f64 / word_len as f64).sqrt() * sum_sq.sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gaussian_breakpoints, mindist, sax};
+
+    #[test]
+    fn gaussian_breakpoints_are_finite_and_increasing() {
+        for alpha in [2, 8, 9, 12, 20] {
+            let breakpoints = gaussian_breakpoints(alpha);
+
+            assert_eq!(breakpoints.len(), alpha - 1);
+            assert!(breakpoints.iter().all(|b| b.is_finite()), "alpha={}: {:?}", alpha, breakpoints);
+            assert!(breakpoints.windows(2).all(|w| w[0] < w[1]), "alpha={}: {:?}", alpha, breakpoints);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid setting for alphabet size")]
+    fn sax_rejects_alpha_above_the_encodable_range() {
+        let data: Vec<f64> = (0..200).map(|i| (i as f64).sin()).collect();
+        sax(&data, 10, 200);
+    }
+
+    #[test]
+    fn mindist_is_zero_for_identical_words_and_positive_for_distant_ones() {
+        assert_eq!(mindist("abcba", "abcba", 100, 3), 0.0);
+
+        // "a" and "c" are two ranks apart (alphabet size 3), so they aren't adjacent and
+        // should contribute a strictly positive cell distance.
+        assert!(mindist("aaa", "ccc", 100, 3) > 0.0);
+
+        // Adjacent symbols never lower-bound to anything but zero.
+        assert_eq!(mindist("aaa", "bbb", 100, 3), 0.0);
+    }
 }
\ No newline at end of file
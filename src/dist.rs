@@ -1,12 +1,111 @@
 use num::Float;
 
-/// Calculates the gaussian distance between two lists of floats.
-pub fn gaussian<N>(q: &[N], c: &[N]) -> N where N: Float {
-    let sum = q
-        .iter()
-        .zip(c)
-        .map(|(qi, ci)| (*qi - *ci).powi(2))
-        .fold(N::from(0.0).unwrap(), |acc, x| acc + x);
-
-    sum.sqrt()
-}
\ No newline at end of file
+/// A pluggable similarity measure between two equal-length subsequences.
+///
+/// The discord-finding entry points are generic over `M: Proximity<N>`, defaulting to
+/// [`Euclidean`] to match the original HOT SAX paper. Implementations don't need to satisfy
+/// the triangle inequality (DTW, for instance, doesn't) unless something relying on
+/// triangle-inequality pruning is layered on top.
+pub trait Proximity<N: Float> {
+    /// Computes the distance between `a` and `b`. Smaller means more similar.
+    fn distance(&self, a: &[N], b: &[N]) -> N;
+
+    /// Like `distance`, but may bail out before finishing once it can prove the result would
+    /// be `>= best_so_far`, returning `None` in that case instead of the exact distance. The
+    /// inner loops pass their running nearest-neighbour distance, so a neighbour that can't
+    /// lower it is abandoned mid-computation.
+    ///
+    /// The default implementation just computes the full distance and compares it; metrics
+    /// that can prune mid-computation (like [`Euclidean`] and [`Dtw`]) override it.
+    fn distance_early_abandon(&self, a: &[N], b: &[N], best_so_far: N) -> Option<N> {
+        let dist = self.distance(a, b);
+        if dist < best_so_far { Some(dist) } else { None }
+    }
+}
+
+/// The Euclidean ("gaussian") distance used by the original HOT SAX paper.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Euclidean;
+
+impl<N: Float + 'static> Proximity<N> for Euclidean {
+    fn distance(&self, a: &[N], b: &[N]) -> N {
+        crate::gaussian(a, b)
+    }
+
+    fn distance_early_abandon(&self, a: &[N], b: &[N], best_so_far: N) -> Option<N> {
+        crate::simd_dist::gaussian_early_abandon(a, b, best_so_far)
+    }
+}
+
+/// Dynamic Time Warping distance computed with a banded DP matrix under a Sakoe-Chiba band
+/// of half-width `window`. Cells outside the band (`|i - j| > window`) are treated as
+/// `+infinity`, so increasing `window` trades speed for the ability to match more warped
+/// alignments.
+#[derive(Debug, Clone, Copy)]
+pub struct Dtw {
+    pub window: usize,
+}
+
+impl Dtw {
+    /// Creates a DTW metric with the given Sakoe-Chiba band half-width.
+    pub fn new(window: usize) -> Self {
+        Self { window }
+    }
+
+    /// Fills the banded DP matrix, calling `on_row` after each completed row with that row's
+    /// minimum value so callers can prune early.
+    fn banded_dtw<N: Float>(&self, a: &[N], b: &[N], mut on_row: impl FnMut(N) -> bool) -> N {
+        let n = a.len();
+        let m = b.len();
+        let inf = N::infinity();
+        let mut d = vec![vec![inf; m]; n];
+        d[0][0] = (a[0] - b[0]).abs();
+
+        for i in 0..n {
+            let lo = i.saturating_sub(self.window);
+            let hi = (i + self.window).min(m - 1);
+            let mut row_min = if i == 0 { d[0][0] } else { inf };
+
+            for j in lo..=hi {
+                if i == 0 && j == 0 {
+                    continue;
+                }
+
+                let cost = (a[i] - b[j]).abs();
+                let up = if i > 0 { d[i-1][j] } else { inf };
+                let left = if j > 0 { d[i][j-1] } else { inf };
+                let diag = if i > 0 && j > 0 { d[i-1][j-1] } else { inf };
+
+                d[i][j] = cost + up.min(left).min(diag);
+                row_min = row_min.min(d[i][j]);
+            }
+
+            if !on_row(row_min) {
+                return row_min;
+            }
+        }
+
+        d[n-1][m-1]
+    }
+}
+
+impl<N: Float> Proximity<N> for Dtw {
+    fn distance(&self, a: &[N], b: &[N]) -> N {
+        self.banded_dtw(a, b, |_| true)
+    }
+
+    fn distance_early_abandon(&self, a: &[N], b: &[N], best_so_far: N) -> Option<N> {
+        let mut abandoned = false;
+
+        let result = self.banded_dtw(a, b, |row_min| {
+            if row_min > best_so_far {
+                abandoned = true;
+                false
+            } else {
+                true
+            }
+        });
+
+        if abandoned { None } else { Some(result) }
+    }
+}
@@ -0,0 +1,99 @@
+//! Approximate matching between SAX words, used to group near-identical words that exact
+//! equality would otherwise treat as unrelated (e.g. a single-symbol jitter like `abcd` vs
+//! `abce` splitting what is really one motif family).
+
+use std::collections::{HashMap, HashSet};
+
+/// The bag of fixed-length substrings ("n-grams") of length `n` found in `word`. Words
+/// shorter than `n` are returned as their own single substring.
+pub fn ngrams(word: &str, n: usize) -> HashSet<String> {
+    if word.chars().count() <= n {
+        return std::iter::once(word.to_string()).collect();
+    }
+
+    let chars: Vec<char> = word.chars().collect();
+    (0..=chars.len() - n)
+        .map(|i| chars[i..i+n].iter().collect())
+        .collect()
+}
+
+/// Scores the similarity between `a` and `b` as the Jaccard similarity (intersection over
+/// union) of their combined n-gram bags across every size in `ngram_sizes`. `1.0` means the
+/// words share every substring; `0.0` means they share none.
+pub fn similarity(a: &str, b: &str, ngram_sizes: &[usize]) -> f64 {
+    let grams_a: HashSet<String> = ngram_sizes.iter().flat_map(|&n| ngrams(a, n)).collect();
+    let grams_b: HashSet<String> = ngram_sizes.iter().flat_map(|&n| ngrams(b, n)).collect();
+    jaccard(&grams_a, &grams_b)
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Groups `words` into clusters of near-identical words: two words end up in the same
+/// cluster once their n-gram similarity (see `similarity`) reaches `threshold`. Passing
+/// `threshold = 1.0` degenerates to exact matching: only identical words are then grouped,
+/// even if two different words happen to have identical n-gram bags.
+///
+/// Candidates are narrowed down first via an inverted index from substring to the word
+/// indexes containing it, so only words sharing at least one substring are ever compared,
+/// rather than every pair.
+///
+/// Returns the clusters as lists of indexes into `words`. Every index appears in exactly one
+/// cluster.
+pub fn fuzzy_cluster(words: &[String], ngram_sizes: &[usize], threshold: f64) -> Vec<Vec<usize>> {
+    let word_ngrams: Vec<HashSet<String>> = words
+        .iter()
+        .map(|word| ngram_sizes.iter().flat_map(|&n| ngrams(word, n)).collect())
+        .collect();
+
+    let mut inverted_index: HashMap<&String, Vec<usize>> = HashMap::new();
+    for (i, grams) in word_ngrams.iter().enumerate() {
+        for gram in grams {
+            inverted_index.entry(gram).or_insert_with(Vec::new).push(i);
+        }
+    }
+
+    let mut assigned = vec![false; words.len()];
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+
+    for i in 0..words.len() {
+        if assigned[i] {
+            continue;
+        }
+
+        let mut candidates: HashSet<usize> = HashSet::new();
+        for gram in &word_ngrams[i] {
+            candidates.extend(inverted_index[gram].iter().copied());
+        }
+
+        let mut cluster = vec![i];
+        assigned[i] = true;
+
+        for j in candidates {
+            if assigned[j] {
+                continue;
+            }
+
+            let matches = if threshold >= 1.0 {
+                words[i] == words[j]
+            } else {
+                jaccard(&word_ngrams[i], &word_ngrams[j]) >= threshold
+            };
+
+            if matches {
+                cluster.push(j);
+                assigned[j] = true;
+            }
+        }
+
+        clusters.push(cluster);
+    }
+
+    clusters
+}
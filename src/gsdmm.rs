@@ -0,0 +1,128 @@
+use rand::Rng;
+use std::collections::HashMap;
+
+type Cluster = Vec<usize>;
+
+/// Clusters SAX words using the Gibbs Sampling Dirichlet Multinomial Mixture model (the
+/// "Movie Group Process", [Yin & Wang 2014](https://dl.acm.org/doi/10.1145/2623330.2623715)).
+///
+/// Each word is treated as a short document whose tokens are its characters, over a vocabulary
+/// of size `vocab_size` (the SAX alphabet). Up to `k` clusters are seeded by assigning every
+/// word to a random cluster, then `maxit` Gibbs sweeps reassign each word according to:
+///
+/// `P(z) ∝ (m_z + alpha) * Π_{w in doc} Π_{j=1..N_w}(n_z^w + beta + j - 1) / Π_{i=1..N_d}(n_z + V*beta + i - 1)`
+///
+/// where `m_z`/`n_z`/`n_z^w` are the per-cluster document count, token count, and per-symbol
+/// counts. `alpha` controls how willing a word is to join a cluster based on its popularity
+/// alone, and `beta` controls how willing it is to join based on symbol overlap. Clusters that
+/// lose every member during sampling are simply absent from the result, so the returned cluster
+/// count is learned rather than fixed at `k`.
+pub fn gsdmm(words: &Vec<String>, vocab_size: usize, k: usize, alpha: f64, beta: f64, maxit: usize) -> Vec<Cluster> {
+    let n = words.len();
+    let docs: Vec<Vec<char>> = words.iter().map(|word| word.chars().collect()).collect();
+
+    let mut rng = rand::thread_rng();
+    let mut z: Vec<usize> = (0..n).map(|_| rng.gen_range(0..k)).collect();
+
+    let mut m_z = vec![0usize; k];
+    let mut n_z = vec![0usize; k];
+    let mut n_z_w: Vec<HashMap<char, usize>> = vec![HashMap::new(); k];
+
+    for (d, doc) in docs.iter().enumerate() {
+        add_doc(doc, z[d], &mut m_z, &mut n_z, &mut n_z_w);
+    }
+
+    for _ in 0..maxit {
+        for (d, doc) in docs.iter().enumerate() {
+            remove_doc(doc, z[d], &mut m_z, &mut n_z, &mut n_z_w);
+
+            let weights: Vec<f64> = (0..k)
+                .map(|cluster| cluster_weight(doc, cluster, vocab_size, alpha, beta, &m_z, &n_z, &n_z_w))
+                .collect();
+
+            let new_z = sample(&weights, &mut rng);
+
+            z[d] = new_z;
+            add_doc(doc, new_z, &mut m_z, &mut n_z, &mut n_z_w);
+        }
+    }
+
+    let mut clusters: Vec<Cluster> = vec![Vec::new(); k];
+    for (d, &cluster) in z.iter().enumerate() {
+        clusters[cluster].push(d);
+    }
+
+    clusters.retain(|cluster| !cluster.is_empty());
+    clusters
+}
+
+fn add_doc(doc: &[char], cluster: usize, m_z: &mut [usize], n_z: &mut [usize], n_z_w: &mut [HashMap<char, usize>]) {
+    m_z[cluster] += 1;
+    n_z[cluster] += doc.len();
+
+    for &c in doc {
+        *n_z_w[cluster].entry(c).or_insert(0) += 1;
+    }
+}
+
+fn remove_doc(doc: &[char], cluster: usize, m_z: &mut [usize], n_z: &mut [usize], n_z_w: &mut [HashMap<char, usize>]) {
+    m_z[cluster] -= 1;
+    n_z[cluster] -= doc.len();
+
+    for &c in doc {
+        let count = n_z_w[cluster].get_mut(&c).unwrap();
+        *count -= 1;
+    }
+}
+
+/// The (unnormalized) probability of `doc` joining `cluster`, per the GSDMM update rule.
+fn cluster_weight(
+    doc: &[char],
+    cluster: usize,
+    vocab_size: usize,
+    alpha: f64,
+    beta: f64,
+    m_z: &[usize],
+    n_z: &[usize],
+    n_z_w: &[HashMap<char, usize>],
+) -> f64 {
+    let mut doc_counts: HashMap<char, usize> = HashMap::new();
+    for &c in doc {
+        *doc_counts.entry(c).or_insert(0) += 1;
+    }
+
+    let mut numerator = 1.0;
+    for (&w, &n_w) in doc_counts.iter() {
+        let n_zw = *n_z_w[cluster].get(&w).unwrap_or(&0) as f64;
+        for j in 1..=n_w {
+            numerator *= n_zw + beta + (j as f64) - 1.0;
+        }
+    }
+
+    let mut denominator = 1.0;
+    for i in 1..=doc.len() {
+        denominator *= n_z[cluster] as f64 + vocab_size as f64 * beta + (i as f64) - 1.0;
+    }
+
+    (m_z[cluster] as f64 + alpha) * numerator / denominator
+}
+
+/// Samples an index from `weights`, treated as unnormalized probabilities. Falls back to a
+/// uniform pick if every weight collapsed to zero.
+fn sample(weights: &[f64], rng: &mut impl Rng) -> usize {
+    let total: f64 = weights.iter().sum();
+
+    if total <= 0.0 {
+        return rng.gen_range(0..weights.len());
+    }
+
+    let mut threshold = rng.gen::<f64>() * total;
+    for (i, &weight) in weights.iter().enumerate() {
+        threshold -= weight;
+        if threshold <= 0.0 {
+            return i;
+        }
+    }
+
+    weights.len() - 1
+}
@@ -0,0 +1,270 @@
+use num::Float;
+use std::collections::HashMap;
+use rand::Rng;
+use crate::dist::{Proximity, Euclidean};
+
+/// A Hierarchical Navigable Small World graph over z-normalized subsequence windows.
+///
+/// Nodes are identified by the starting index of the window they represent, so the graph
+/// can be queried directly with the same indexes used by the discord-finding loops. Each
+/// node lives in every layer from `0` up to a randomly drawn top layer, with per-layer
+/// adjacency lists capped at `m` neighbours (`2*m` on layer 0, following the original paper).
+///
+/// Distances are approximate: queries only ever inspect a bounded neighbourhood, so the
+/// nearest neighbour returned may not be the true nearest neighbour in the index. Distances
+/// are computed with `M`, defaulting to [`Euclidean`] to match the rest of the crate.
+pub struct HnswIndex<N: Float, M: Proximity<N> = Euclidean> {
+    vectors: HashMap<usize, Vec<N>>,
+    layers: Vec<HashMap<usize, Vec<usize>>>,
+    node_top_layer: HashMap<usize, usize>,
+    entry_point: Option<usize>,
+    m: usize,
+    ef_construction: usize,
+    level_mult: f64,
+    metric: M,
+}
+
+impl<N: Float, M: Proximity<N>> HnswIndex<N, M> {
+    /// Creates an empty index using `metric` to compare windows. `m` bounds the out-degree
+    /// of each node (doubled on layer 0), and `ef_construction` is the candidate-set size
+    /// used while linking new nodes in.
+    pub fn new(m: usize, ef_construction: usize, metric: M) -> Self {
+        Self {
+            vectors: HashMap::new(),
+            layers: Vec::new(),
+            node_top_layer: HashMap::new(),
+            entry_point: None,
+            m: m.max(1),
+            ef_construction: ef_construction.max(1),
+            level_mult: 1.0 / (m.max(2) as f64).ln(),
+            metric,
+        }
+    }
+
+    fn random_level(&self) -> usize {
+        let u: f64 = rand::thread_rng().gen_range(f64::MIN_POSITIVE..1.0);
+        (-u.ln() * self.level_mult).floor() as usize
+    }
+
+    fn distance(&self, a: &[N], b: &[N]) -> f64 {
+        self.metric.distance(a, b).to_f64().unwrap()
+    }
+
+    /// Reject neighbours that would be a trivial self-match: any `j` with `|id - j| < exclude_radius`.
+    fn is_trivial_match(id: usize, other: usize, exclude_radius: usize) -> bool {
+        (id as isize - other as isize).abs() < exclude_radius as isize
+    }
+
+    /// Greedily searches a single layer starting from `entry`, keeping the closest `ef`
+    /// candidates found. `query_id`, when set, is excluded (alongside trivial matches) from
+    /// the candidate set, which is how we answer "nearest neighbour of an existing node".
+    fn search_layer(
+        &self,
+        query: &[N],
+        entry: usize,
+        ef: usize,
+        layer: usize,
+        exclude_radius: usize,
+        query_id: Option<usize>,
+    ) -> Vec<(f64, usize)> {
+        let mut visited = std::collections::HashSet::new();
+        visited.insert(entry);
+
+        let mut candidates: Vec<(f64, usize)> =
+            vec![(self.distance(query, &self.vectors[&entry]), entry)];
+
+        // The seed still drives traversal (it's where expansion starts from), but it must pass
+        // the same trivial-match filter as every expanded neighbour below before it's allowed
+        // into the result set, or a trivial match can sneak into `best`/`candidates`' caller
+        // undetected.
+        let entry_is_trivial = query_id.is_some_and(|qid| entry == qid || Self::is_trivial_match(qid, entry, exclude_radius));
+        let mut best: Vec<(f64, usize)> = if entry_is_trivial { Vec::new() } else { candidates.clone() };
+
+        while let Some(&(dist, node)) = candidates.iter().min_by(|a, b| a.0.partial_cmp(&b.0).unwrap()) {
+            candidates.retain(|&(_, n)| n != node);
+
+            let worst_best = best
+                .iter()
+                .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+                .map(|&(d, _)| d)
+                .unwrap_or(f64::INFINITY);
+            if best.len() >= ef && dist > worst_best {
+                break;
+            }
+
+            let neighbours = match self.layers[layer].get(&node) {
+                Some(n) => n.clone(),
+                None => continue,
+            };
+
+            for neighbour in neighbours {
+                if visited.contains(&neighbour) {
+                    continue;
+                }
+                visited.insert(neighbour);
+
+                if let Some(qid) = query_id {
+                    if neighbour == qid || Self::is_trivial_match(qid, neighbour, exclude_radius) {
+                        continue;
+                    }
+                }
+
+                let d = self.distance(query, &self.vectors[&neighbour]);
+                candidates.push((d, neighbour));
+                best.push((d, neighbour));
+                best.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                best.truncate(ef);
+            }
+        }
+
+        best
+    }
+
+    /// Inserts `vector` under `id` (typically the window's start index). `exclude_radius`
+    /// prevents the node from linking to trivial-match neighbours (`|id - other| < exclude_radius`)
+    /// both while searching for insertion candidates and while pruning the resulting edges.
+    pub fn insert(&mut self, id: usize, vector: Vec<N>, exclude_radius: usize) {
+        let top_layer = self.random_level();
+        self.vectors.insert(id, vector.clone());
+        self.node_top_layer.insert(id, top_layer);
+
+        while self.layers.len() <= top_layer {
+            self.layers.push(HashMap::new());
+        }
+
+        let entry_point = match self.entry_point {
+            Some(ep) => ep,
+            None => {
+                self.entry_point = Some(id);
+                for layer in 0..=top_layer {
+                    self.layers[layer].entry(id).or_insert_with(Vec::new);
+                }
+                return;
+            }
+        };
+
+        let entry_top = self.node_top_layer[&entry_point];
+        let mut curr = entry_point;
+
+        // Descend greedily from the current top layer down to `top_layer + 1`.
+        for layer in (top_layer + 1..=entry_top).rev() {
+            let found = self.search_layer(&vector, curr, 1, layer, exclude_radius, Some(id));
+            if let Some(&(_, node)) = found.first() {
+                curr = node;
+            }
+        }
+
+        // Link the node in at every layer it belongs to, from `top_layer` down to `0`.
+        for layer in (0..=top_layer.min(entry_top)).rev() {
+            let cap = if layer == 0 { 2 * self.m } else { self.m };
+            let candidates = self.search_layer(&vector, curr, self.ef_construction, layer, exclude_radius, Some(id));
+
+            let mut neighbours: Vec<(f64, usize)> = candidates;
+            neighbours.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            neighbours.truncate(cap);
+
+            let selected: Vec<usize> = neighbours.iter().map(|&(_, n)| n).collect();
+            self.layers[layer].insert(id, selected.clone());
+
+            for &neighbour in &selected {
+                // Cloned out (rather than mutated through the `entry` borrow directly) since the
+                // pruning sort below needs `&self.vectors`/`self.distance`, which would otherwise
+                // overlap with the mutable borrow of `self.layers` held by the entry.
+                let mut back_links = self.layers[layer].entry(neighbour).or_insert_with(Vec::new).clone();
+                if !back_links.contains(&id) {
+                    back_links.push(id);
+                }
+                if back_links.len() > cap {
+                    let neighbour_vec = self.vectors[&neighbour].clone();
+                    back_links.sort_by(|&a, &b| {
+                        self.distance(&neighbour_vec, &self.vectors[&a])
+                            .partial_cmp(&self.distance(&neighbour_vec, &self.vectors[&b]))
+                            .unwrap()
+                    });
+                    back_links.truncate(cap);
+                }
+                self.layers[layer].insert(neighbour, back_links);
+            }
+
+            if let Some(&(_, closest)) = neighbours.first() {
+                curr = closest;
+            }
+        }
+
+        // Only nodes at or above `top_layer` need an entry in the higher, still-empty layers.
+        for layer in 0..=top_layer {
+            self.layers[layer].entry(id).or_insert_with(Vec::new);
+        }
+
+        if top_layer > entry_top {
+            self.entry_point = Some(id);
+        }
+    }
+
+    /// Approximately finds the nearest non-trivial-match neighbour of an already-inserted node,
+    /// widening to `ef` candidates once the search reaches layer 0.
+    pub fn query_nearest(&self, id: usize, ef: usize, exclude_radius: usize) -> Option<(f64, usize)> {
+        let entry_point = self.entry_point?;
+        let query = self.vectors.get(&id)?.clone();
+        let entry_top = self.node_top_layer[&entry_point];
+
+        let mut curr = entry_point;
+        for layer in (1..=entry_top).rev() {
+            let found = self.search_layer(&query, curr, 1, layer, exclude_radius, Some(id));
+            if let Some(&(_, node)) = found.first() {
+                curr = node;
+            }
+        }
+
+        let found = self.search_layer(&query, curr, ef.max(1), 0, exclude_radius, Some(id));
+        found
+            .into_iter()
+            .filter(|&(_, node)| !Self::is_trivial_match(id, node, exclude_radius))
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HnswIndex;
+    use crate::dist::Euclidean;
+
+    #[test]
+    fn insert_never_links_trivial_match_edges() {
+        let exclude_radius = 5;
+        let mut index = HnswIndex::new(4, 8, Euclidean);
+
+        for i in 0..60 {
+            let vector = vec![(i as f64 * 0.1).sin()];
+            index.insert(i, vector, exclude_radius);
+        }
+
+        for layer in &index.layers {
+            for (&id, neighbours) in layer {
+                for &neighbour in neighbours {
+                    assert!(
+                        !HnswIndex::<f64, Euclidean>::is_trivial_match(id, neighbour, exclude_radius),
+                        "trivial-match edge {} -> {} (exclude_radius {})", id, neighbour, exclude_radius
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn query_nearest_does_not_spuriously_miss_candidates() {
+        let exclude_radius = 5;
+        let mut index = HnswIndex::new(4, 8, Euclidean);
+
+        for i in 0..60 {
+            let vector = vec![(i as f64 * 0.1).sin()];
+            index.insert(i, vector, exclude_radius);
+        }
+
+        let misses = (0..60)
+            .filter(|&i| index.query_nearest(i, 8, exclude_radius).is_none())
+            .count();
+
+        assert_eq!(misses, 0, "query_nearest found no non-trivial neighbour for {} of 60 nodes", misses);
+    }
+}
@@ -3,9 +3,27 @@
 //! It will also include the [HS-Squeezer](https://dl.acm.org/doi/abs/10.1145/3287921.3287929) algorithm when it is implemented,
 //! since it offers useful optimizations, while still being heavily based on the HOT SAX algorithm.
 //!
+//! For series too long for the exact algorithms above to be practical, `Algorithm::HnswApprox`
+//! trades exactness for speed by answering nearest-neighbor queries from a Hierarchical
+//! Navigable Small World graph over the sliding-window subsequences instead of an exhaustive
+//! scan.
+//!
+//! As an alternative to `squeezer`'s clustering, `Algorithm::Gsdmm` groups SAX words with the
+//! Gibbs Sampling Dirichlet Multinomial Mixture model, which often yields cleaner groupings of
+//! short symbolic words than the single-pass greedy heuristic.
+//!
 //! During the implementation some other functions had to be made, such as `paa`, `znorm`, and
 //! `gaussian`. These functions are exposed, due to their utility apart from being used in HOT SAX.
 //!
+//! `mindist` computes the standard SAX lower-bound distance between two SAX words, letting
+//! callers prune whole trie buckets before ever comparing the underlying subsequences.
+//!
+//! `source::SubsequenceSource` and `source::DiskSeries` let a series be read window-by-window
+//! from disk instead of being loaded into memory up front, for recordings too large to fit in
+//! RAM. `source::find_largest_discord`/`find_n_largest_discords` run the brute-force algorithm
+//! directly over a source; `Anomaly` and its other algorithms aren't wired up to stream from
+//! one yet (see the module docs for why, and what's left).
+//!
 //! The code is well commented in order to explain the implementation, in the case that people want
 //! to learn how the HOT SAX algorithm works by looking at an implementation. If a part is vaguely
 //! commented, feel free to leave an issue.
@@ -72,7 +90,7 @@ pub use anomaly::Anomaly;
 ///
 /// Used in the implementation of `HOTSAX`, but can be used externally as well.
 pub mod dim_reduction;
-pub use dim_reduction::{paa, sax};
+pub use dim_reduction::{paa, sax, mindist};
 
 /// Miscellaneous utility functions.
 pub mod util;
@@ -81,10 +99,33 @@ pub use util::{gaussian, znorm, mean, std_dev};
 /// Clustering functions and squeezer impl
 pub mod squeezer;
 pub use squeezer::squeezer;
-pub use anomaly::Algorithm;
+pub use anomaly::{Algorithm, ChosenAlgorithm};
+
+/// GSDMM (Movie Group Process) clustering, an alternative to `squeezer`.
+pub mod gsdmm;
+pub use gsdmm::gsdmm;
+
+/// Approximate ("fuzzy") matching between SAX words, for grouping near-identical words that
+/// exact equality would otherwise treat as unrelated.
+pub mod fuzzy_match;
+pub use fuzzy_match::{fuzzy_cluster, similarity as fuzzy_similarity};
+
+/// A disk-backed alternative to an in-memory series, for recordings too large to load at once.
+pub mod source;
+pub use source::{SubsequenceSource, DiskSeries};
 
 pub(crate) mod trie;
 
+pub(crate) mod hnsw;
+
+pub(crate) mod vp_tree;
+
+pub(crate) mod simd_dist;
+
+/// Pluggable distance metrics usable in place of the default Euclidean ("gaussian") distance.
+pub mod dist;
+pub use dist::{Proximity, Euclidean, Dtw};
+
 #[cfg(test)]
 mod test {
     use plotly::{Plot, Scatter, Layout};
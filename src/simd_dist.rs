@@ -0,0 +1,207 @@
+//! A feature-gated, explicitly vectorized variant of [`crate::gaussian`] for the innermost
+//! distance comparisons in `anomaly::inner_algo`.
+//!
+//! Enable the `simd` feature to use explicit AVX intrinsics (`std::arch::x86_64`) on `f32`/`f64`
+//! slices when the host CPU supports AVX at runtime, falling back to the portable scalar loop
+//! otherwise (including on non-`x86_64` targets, or for any `N: Float` that isn't `f32`/`f64`).
+//! Every variant supports early abandoning: once the running sum of squared differences
+//! exceeds `best_dist * best_dist`, the comparison stops and returns `None` instead of
+//! finishing the pass and taking the square root.
+
+use num::Float;
+#[cfg(feature = "simd")]
+use std::any::TypeId;
+
+/// Computes the Euclidean distance between `q` and `c`, stopping as soon as the running sum
+/// of squared differences exceeds `best_dist * best_dist`. Returns `None` when abandoned,
+/// `Some(distance)` otherwise.
+///
+/// Dispatches to an explicit SIMD kernel when compiled with the `simd` feature and `N` is
+/// `f32`/`f64`; otherwise falls back to the portable scalar loop.
+pub fn gaussian_early_abandon<N: Float + 'static>(q: &[N], c: &[N], best_dist: N) -> Option<N> {
+    #[cfg(feature = "simd")]
+    {
+        if TypeId::of::<N>() == TypeId::of::<f64>() {
+            // SAFETY: `N` is `f64`, verified above, so reinterpreting the slices is sound.
+            let q64: &[f64] = unsafe { std::mem::transmute(q) };
+            let c64: &[f64] = unsafe { std::mem::transmute(c) };
+            let best64 = best_dist.to_f64().unwrap();
+            return simd::gaussian_f64(q64, c64, best64).map(|d| N::from(d).unwrap());
+        }
+
+        if TypeId::of::<N>() == TypeId::of::<f32>() {
+            // SAFETY: `N` is `f32`, verified above, so reinterpreting the slices is sound.
+            let q32: &[f32] = unsafe { std::mem::transmute(q) };
+            let c32: &[f32] = unsafe { std::mem::transmute(c) };
+            let best32 = best_dist.to_f32().unwrap();
+            return simd::gaussian_f32(q32, c32, best32).map(|d| N::from(d).unwrap());
+        }
+    }
+
+    scalar_early_abandon(q, c, best_dist)
+}
+
+/// Portable scalar early-abandoning Euclidean distance, used for any `N: Float` that the
+/// SIMD kernels don't specialize for, and whenever the `simd` feature is disabled.
+fn scalar_early_abandon<N: Float>(q: &[N], c: &[N], best_dist: N) -> Option<N> {
+    let threshold = best_dist * best_dist;
+    let mut sum = N::zero();
+
+    for (qi, ci) in q.iter().zip(c) {
+        sum = sum + (*qi - *ci).powi(2);
+        if sum > threshold {
+            return None;
+        }
+    }
+
+    Some(sum.sqrt())
+}
+
+#[cfg(feature = "simd")]
+mod simd {
+    /// Processes `f64` slices 4 lanes (AVX, 256-bit) at a time via explicit `std::arch`
+    /// intrinsics when the host CPU supports AVX, falling back to the scalar remainder loop
+    /// for the tail and for the whole slice when AVX isn't available.
+    pub fn gaussian_f64(q: &[f64], c: &[f64], best_dist: f64) -> Option<f64> {
+        let threshold = best_dist * best_dist;
+
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx") {
+            const LANES: usize = 4;
+            let mut sum = 0.0_f64;
+            let chunks = q.len() / LANES;
+
+            for chunk in 0..chunks {
+                let base = chunk * LANES;
+                // SAFETY: AVX support was just verified above, and `base+LANES <= q.len()`.
+                let partial = unsafe { x86::sum_sq_f64x4(&q[base..base+LANES], &c[base..base+LANES]) };
+                sum += partial;
+                if sum > threshold {
+                    return None;
+                }
+            }
+
+            return super::scalar_early_abandon(&q[chunks*LANES..], &c[chunks*LANES..], (threshold - sum).max(0.0).sqrt())
+                .map(|rest| (sum + rest*rest).sqrt());
+        }
+
+        super::scalar_early_abandon(q, c, best_dist)
+    }
+
+    /// Processes `f32` slices 8 lanes (AVX, 256-bit) at a time via explicit `std::arch`
+    /// intrinsics when the host CPU supports AVX, falling back to the scalar remainder loop
+    /// for the tail and for the whole slice when AVX isn't available.
+    pub fn gaussian_f32(q: &[f32], c: &[f32], best_dist: f32) -> Option<f32> {
+        let threshold = best_dist * best_dist;
+
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx") {
+            const LANES: usize = 8;
+            let mut sum = 0.0_f32;
+            let chunks = q.len() / LANES;
+
+            for chunk in 0..chunks {
+                let base = chunk * LANES;
+                // SAFETY: AVX support was just verified above, and `base+LANES <= q.len()`.
+                let partial = unsafe { x86::sum_sq_f32x8(&q[base..base+LANES], &c[base..base+LANES]) };
+                sum += partial;
+                if sum > threshold {
+                    return None;
+                }
+            }
+
+            return super::scalar_early_abandon(&q[chunks*LANES..], &c[chunks*LANES..], (threshold - sum).max(0.0).sqrt())
+                .map(|rest| (sum + rest*rest).sqrt());
+        }
+
+        super::scalar_early_abandon(q, c, best_dist)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    mod x86 {
+        use std::arch::x86_64::*;
+
+        /// Computes `sum((q[i] - c[i])^2)` over exactly 4 `f64` lanes with AVX, reducing the
+        /// 256-bit vector down to a single scalar horizontally.
+        ///
+        /// # Safety
+        /// The caller must have verified `is_x86_feature_detected!("avx")`, and `q`/`c` must
+        /// each have exactly 4 elements.
+        #[target_feature(enable = "avx")]
+        pub unsafe fn sum_sq_f64x4(q: &[f64], c: &[f64]) -> f64 {
+            let qv = _mm256_loadu_pd(q.as_ptr());
+            let cv = _mm256_loadu_pd(c.as_ptr());
+            let d = _mm256_sub_pd(qv, cv);
+            let sq = _mm256_mul_pd(d, d);
+
+            // Horizontal reduction: fold the high 128 bits into the low 128 bits, then the
+            // high 64 bits into the low 64 bits.
+            let hi = _mm256_extractf128_pd(sq, 1);
+            let lo = _mm256_castpd256_pd128(sq);
+            let sum128 = _mm_add_pd(hi, lo);
+            let shuf = _mm_unpackhi_pd(sum128, sum128);
+            let result = _mm_add_sd(sum128, shuf);
+            _mm_cvtsd_f64(result)
+        }
+
+        /// Computes `sum((q[i] - c[i])^2)` over exactly 8 `f32` lanes with AVX, reducing the
+        /// 256-bit vector down to a single scalar horizontally.
+        ///
+        /// # Safety
+        /// The caller must have verified `is_x86_feature_detected!("avx")`, and `q`/`c` must
+        /// each have exactly 8 elements.
+        #[target_feature(enable = "avx")]
+        pub unsafe fn sum_sq_f32x8(q: &[f32], c: &[f32]) -> f32 {
+            let qv = _mm256_loadu_ps(q.as_ptr());
+            let cv = _mm256_loadu_ps(c.as_ptr());
+            let d = _mm256_sub_ps(qv, cv);
+            let sq = _mm256_mul_ps(d, d);
+
+            // Horizontal reduction: two rounds of pairwise add collapses all 8 lanes into
+            // the low lane of each 128-bit half, which are then added together.
+            let sum1 = _mm256_hadd_ps(sq, sq);
+            let sum2 = _mm256_hadd_ps(sum1, sum1);
+            let lo = _mm256_castps256_ps128(sum2);
+            let hi = _mm256_extractf128_ps(sum2, 1);
+            let result = _mm_add_ss(lo, hi);
+            _mm_cvtss_f32(result)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "simd"))]
+mod tests {
+    use super::gaussian_early_abandon;
+    use crate::gaussian;
+
+    #[test]
+    fn simd_f64_matches_scalar_gaussian() {
+        let q: Vec<f64> = (0..37).map(|i| (i as f64 * 0.37).sin()).collect();
+        let c: Vec<f64> = (0..37).map(|i| (i as f64 * 0.29).cos()).collect();
+
+        let expected = gaussian(&q, &c);
+        let actual = gaussian_early_abandon(&q, &c, f64::INFINITY).unwrap();
+
+        assert!((expected - actual).abs() < 1e-9, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn simd_f32_matches_scalar_gaussian() {
+        let q: Vec<f32> = (0..37).map(|i| (i as f32 * 0.37).sin()).collect();
+        let c: Vec<f32> = (0..37).map(|i| (i as f32 * 0.29).cos()).collect();
+
+        let expected = gaussian(&q, &c);
+        let actual = gaussian_early_abandon(&q, &c, f32::INFINITY).unwrap();
+
+        assert!((expected - actual).abs() < 1e-4, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn simd_early_abandon_agrees_with_scalar_on_abandonment() {
+        let q: Vec<f64> = (0..41).map(|i| i as f64).collect();
+        let c: Vec<f64> = (0..41).map(|i| -(i as f64)).collect();
+
+        // A tiny best_dist should cause both paths to abandon.
+        assert_eq!(gaussian_early_abandon(&q, &c, 0.001), None);
+    }
+}
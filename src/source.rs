@@ -0,0 +1,251 @@
+//! A lazily-loaded alternative to an in-memory `&Vec<N>` series, for recordings too large to
+//! fit in memory.
+//!
+//! [`SubsequenceSource`] is the common interface: given a window's `start` and length `n`, fill
+//! a caller-owned `buf` rather than handing back a borrowed slice, so a source never needs to
+//! hold (or even have loaded) the whole series at once. [`DiskSeries`] is the disk-backed
+//! implementation, reading packed `f32`/`f64` values straight from a flat binary file with a
+//! seek + `read_exact` per window instead of `mmap`-ing it, so it works the same on every
+//! platform and never maps more of the file than the current window needs.
+//!
+//! Most discord-search entry points in `anomaly` are generic over `R: Deref<Target=[N]>`, which
+//! assumes a window can be borrowed as a contiguous slice — something a disk-backed source
+//! fundamentally can't offer without copying. Wiring `SubsequenceSource` through the trie
+//! build, `znorm`, and every distance call in HOT SAX, Squeezer, GSDMM, HNSW, and the VP-tree
+//! would mean threading a reusable buffer through the entire algorithm suite instead of
+//! borrowing slices directly — a larger change than this source abstraction itself, and one
+//! that isn't done here.
+//!
+//! Brute force is the exception: it never needs more than two windows resident at once, so
+//! [`find_n_largest_discords`] runs it directly over a `SubsequenceSource`, making this the
+//! first algorithm actually able to search a series that never lives fully in memory (see
+//! `DiskSeries`). `Anomaly::with` itself is not yet one of its callers — that integration,
+//! along with the remaining algorithms above, is tracked as follow-up work, not delivered by
+//! this module.
+
+use num::Float;
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use crate::dist::Proximity;
+use crate::util::{RangeSet, TopKDiscords};
+
+/// A series of `N`-valued samples that can be read window-by-window without necessarily being
+/// held in memory all at once.
+pub trait SubsequenceSource<N: Float> {
+    /// The total number of samples in the series.
+    fn len(&self) -> usize;
+
+    /// Fills `buf` with the `buf.len()` samples starting at `start`.
+    ///
+    /// # Panics
+    /// - if `start + buf.len()` is greater than `self.len()`.
+    fn subsequence(&self, start: usize, buf: &mut [N]);
+}
+
+impl<N: Float + Copy> SubsequenceSource<N> for [N] {
+    fn len(&self) -> usize {
+        <[N]>::len(self)
+    }
+
+    fn subsequence(&self, start: usize, buf: &mut [N]) {
+        buf.copy_from_slice(&self[start..start+buf.len()]);
+    }
+}
+
+impl<N: Float + Copy> SubsequenceSource<N> for Vec<N> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn subsequence(&self, start: usize, buf: &mut [N]) {
+        <[N] as SubsequenceSource<N>>::subsequence(self.as_slice(), start, buf)
+    }
+}
+
+/// A series backed by a flat file of packed `f32`/`f64` values, read on demand instead of being
+/// loaded into memory up front.
+///
+/// Each `subsequence` call seeks to `start * size_of::<N>()` and reads `buf.len() * size_of::<N>()`
+/// bytes into a reusable internal byte buffer before converting them into `buf`, so repeated
+/// window reads never allocate.
+pub struct DiskSeries<N> {
+    file: RefCell<File>,
+    byte_buf: RefCell<Vec<u8>>,
+    len: usize,
+    _marker: PhantomData<N>,
+}
+
+impl<N: Float + 'static> DiskSeries<N> {
+    /// Opens `path` as a flat file of packed `N` values (`f32` or `f64`). `len()` is derived
+    /// from the file size.
+    ///
+    /// # Panics
+    /// - if `N` isn't `f32` or `f64`.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let width = sample_width::<N>();
+        let file = File::open(path)?;
+        let byte_len = file.metadata()?.len() as usize;
+
+        Ok(Self {
+            file: RefCell::new(file),
+            byte_buf: RefCell::new(Vec::new()),
+            len: byte_len / width,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<N: Float + 'static> SubsequenceSource<N> for DiskSeries<N> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn subsequence(&self, start: usize, buf: &mut [N]) {
+        let width = sample_width::<N>();
+
+        let mut file = self.file.borrow_mut();
+        let mut byte_buf = self.byte_buf.borrow_mut();
+        byte_buf.resize(buf.len() * width, 0);
+
+        file.seek(SeekFrom::Start((start * width) as u64)).expect("seek past end of DiskSeries");
+        file.read_exact(&mut byte_buf).expect("read past end of DiskSeries");
+
+        for (chunk, out) in byte_buf.chunks_exact(width).zip(buf.iter_mut()) {
+            *out = decode_sample(chunk);
+        }
+    }
+}
+
+fn sample_width<N: Float + 'static>() -> usize {
+    if TypeId::of::<N>() == TypeId::of::<f64>() {
+        8
+    } else if TypeId::of::<N>() == TypeId::of::<f32>() {
+        4
+    } else {
+        panic!("DiskSeries only supports f32 and f64 samples");
+    }
+}
+
+fn decode_sample<N: Float + 'static>(bytes: &[u8]) -> N {
+    if TypeId::of::<N>() == TypeId::of::<f64>() {
+        let value = f64::from_le_bytes(bytes.try_into().unwrap());
+        N::from(value).unwrap()
+    } else {
+        let value = f32::from_le_bytes(bytes.try_into().unwrap());
+        N::from(value).unwrap()
+    }
+}
+
+/// Finds the top `n` largest discords by running the brute-force algorithm directly over
+/// `source`, the way `anomaly_internal::brute_force_top_n` does over an in-memory slice. The
+/// vector returned can have *less* than `n` elements if fewer discords could be found.
+pub fn find_n_largest_discords<N, S, M>(
+    source: &S,
+    discord_size: usize,
+    discord_amnt: usize,
+    metric: &M,
+) -> Vec<(f64, usize)> where N: Float, S: SubsequenceSource<N>, M: Proximity<N> {
+    let mut discords = TopKDiscords::new(discord_amnt, discord_size);
+    let mut skip_over = RangeSet::new();
+
+    loop {
+        let discord = brute_force_internal(source, discord_size, &skip_over, metric);
+
+        if discord.0 == 0.0 {
+            break discords.into_vec()
+        }
+
+        discords.try_insert(discord.0, discord.1);
+
+        if discords.len() >= discord_amnt {
+            break discords.into_vec()
+        }
+
+        let min = 0.max(discord.1 as isize - discord_size as isize) as usize;
+        skip_over.insert_range(min, discord.1 + discord_size);
+    }
+}
+
+/// Finds the largest discord by running the brute-force algorithm directly over `source`. If
+/// one couldn't be found, this function returns `None` instead.
+pub fn find_largest_discord<N, S, M>(
+    source: &S,
+    discord_size: usize,
+    metric: &M,
+) -> Option<(f64, usize)> where N: Float, S: SubsequenceSource<N>, M: Proximity<N> {
+    find_n_largest_discords(source, discord_size, 1, metric).pop()
+}
+
+/// Mirrors `anomaly_internal::brute_force_internal`, but reads each pair of windows into
+/// `q_buf`/`c_buf` via `SubsequenceSource::subsequence` instead of borrowing them out of a
+/// resident slice, so `source` never needs more than two windows in memory at once.
+fn brute_force_internal<N, S, M>(
+    source: &S,
+    n: usize,
+    skip_over: &RangeSet,
+    metric: &M,
+) -> (f64, usize) where N: Float, S: SubsequenceSource<N>, M: Proximity<N> {
+    let candidate_amnt = source.len().saturating_sub(n) + 1;
+
+    let mut q_buf = vec![N::zero(); n-1];
+    let mut c_buf = vec![N::zero(); n-1];
+
+    let mut best_dist = 0.0;
+    let mut best_loc = 0;
+
+    for i in 0..candidate_amnt {
+        if skip_over.contains(i) { continue }
+        source.subsequence(i, &mut q_buf);
+
+        let mut neigh_dist = f64::INFINITY;
+        for j in 0..candidate_amnt {
+            if (i as isize - j as isize).abs() >= n as isize {
+                source.subsequence(j, &mut c_buf);
+
+                // Bails out mid-computation once the partial sum already exceeds the
+                // current running minimum, since such a `j` can't lower `neigh_dist` anyway.
+                if let Some(dist) = metric.distance_early_abandon(
+                    &q_buf,
+                    &c_buf,
+                    N::from(neigh_dist).unwrap_or_else(N::infinity)
+                ) {
+                    neigh_dist = neigh_dist.min(dist.to_f64().unwrap());
+                }
+            }
+        }
+
+        if neigh_dist > best_dist {
+            best_dist = neigh_dist;
+            best_loc = i;
+        }
+    }
+
+    (best_dist, best_loc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_n_largest_discords;
+    use crate::dist::Euclidean;
+
+    #[test]
+    fn finds_the_obvious_discord_over_a_vec_source() {
+        // A near-constant series with one spike: the window straddling the spike should be
+        // the only discord reported.
+        let mut data = vec![0.0; 100];
+        for (i, v) in data.iter_mut().enumerate().skip(50).take(5) {
+            *v = 10.0 + i as f64;
+        }
+
+        let discords = find_n_largest_discords(&data, 10, 1, &Euclidean);
+
+        assert_eq!(discords.len(), 1);
+        let (_, loc) = discords[0];
+        assert!((45..55).contains(&loc), "expected the discord near the spike, got {}", loc);
+    }
+}
@@ -1,7 +1,7 @@
 use num::Float;
 use std::ops::{Deref, DerefMut};
 
-type Cluster = Vec<usize>;
+pub(crate) type Cluster = Vec<usize>;
 
 pub fn squeezer(data: &Vec<String>, threshold: f64) -> Vec<Cluster> {
     let mut clusters: Vec<Cluster> = vec![vec![0]];
@@ -27,6 +27,11 @@ pub fn znorm<R, N>(data: &R) -> Vec<N> where R: Deref<Target=[N]>, N: Float {
 }
 
 /// Calculates the gaussian distance between two lists of floats.
+///
+/// For the innermost discord-search loops, where only "is this closer than the current best"
+/// matters, prefer `crate::simd_dist::gaussian_early_abandon`, which skips the remaining
+/// elements and the final `sqrt` as soon as the running sum of squared differences proves the
+/// result can't beat the best distance found so far.
 pub fn gaussian<N>(q: &[N], c: &[N]) -> N where N: Float {
     let sum = q
         .iter()
@@ -35,4 +40,157 @@ pub fn gaussian<N>(q: &[N], c: &[N]) -> N where N: Float {
         .fold(N::zero(), |acc, x| acc + x);
 
     sum.sqrt()
+}
+
+/// A set of `usize` indexes represented as a sorted list of non-overlapping, half-open
+/// `[start, end)` intervals.
+///
+/// Used by the iterative `*_top_n`/`*_min_dist` discord loops to track already-claimed
+/// exclusion zones. Unlike a plain `Vec<usize>` of individual indexes, `contains` runs in
+/// `O(log k)` time, where `k` is the number of disjoint ranges, rather than
+/// `O(total excluded indexes)`. `insert_range` binary-searches its position in `O(log k)`, but
+/// splicing the merged range into the `Vec` can shift up to `O(k)` later ranges.
+#[derive(Debug, Default)]
+pub(crate) struct RangeSet {
+    ranges: Vec<(usize, usize)>,
+}
+
+impl RangeSet {
+    /// Creates an empty `RangeSet`.
+    pub(crate) fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Inserts the half-open range `[start, end)`, merging it with any ranges it now
+    /// overlaps or touches.
+    pub(crate) fn insert_range(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+
+        // First range that could possibly overlap or touch `start` from the left.
+        let mut lo = self.ranges.partition_point(|&(s, _)| s <= start);
+        if lo > 0 && self.ranges[lo-1].1 >= start {
+            lo -= 1;
+        }
+
+        let mut merged_start = start;
+        let mut merged_end = end;
+        let mut hi = lo;
+
+        while hi < self.ranges.len() && self.ranges[hi].0 <= merged_end {
+            merged_start = merged_start.min(self.ranges[hi].0);
+            merged_end = merged_end.max(self.ranges[hi].1);
+            hi += 1;
+        }
+
+        self.ranges.splice(lo..hi, std::iter::once((merged_start, merged_end)));
+    }
+
+    /// Returns `true` if `i` falls within any of the stored ranges.
+    pub(crate) fn contains(&self, i: usize) -> bool {
+        let pos = self.ranges.partition_point(|&(s, _)| s <= i);
+        if pos == 0 {
+            return false;
+        }
+
+        let (_, end) = self.ranges[pos-1];
+        i < end
+    }
+}
+
+/// A bounded buffer of the `k` largest-distance discords seen so far, kept sorted by
+/// distance descending.
+///
+/// Follows the in-place merge pattern used by nearest-neighbour libraries' `merge_k_nearest`:
+/// a candidate is inserted at its sorted position and the buffer is truncated back to `k`,
+/// rather than being pushed onto an unbounded `Vec` and sorted/truncated at the end.
+/// Candidates within `discord_size` of an already-accepted location are rejected outright,
+/// enforcing non-overlap between the discords that make it into the buffer.
+#[derive(Debug)]
+pub(crate) struct TopKDiscords {
+    k: usize,
+    discord_size: usize,
+    entries: Vec<(f64, usize)>,
+}
+
+impl TopKDiscords {
+    /// Creates an empty buffer that keeps at most the `k` largest discords, rejecting any
+    /// candidate within `discord_size` of one already accepted.
+    pub(crate) fn new(k: usize, discord_size: usize) -> Self {
+        Self { k, discord_size, entries: Vec::with_capacity(k) }
+    }
+
+    /// Attempts to insert `(dist, loc)`, returning `true` if it was accepted. Rejects `loc`
+    /// if it overlaps an already-accepted location, or if the buffer is full and `dist` isn't
+    /// larger than the current worst entry.
+    pub(crate) fn try_insert(&mut self, dist: f64, loc: usize) -> bool {
+        let overlaps = self.entries
+            .iter()
+            .any(|&(_, l)| (l as isize - loc as isize).abs() < self.discord_size as isize);
+        if overlaps {
+            return false;
+        }
+
+        if self.entries.len() >= self.k {
+            let worst = self.entries.last().unwrap().0;
+            if dist <= worst {
+                return false;
+            }
+        }
+
+        let pos = self.entries.partition_point(|&(d, _)| d > dist);
+        self.entries.insert(pos, (dist, loc));
+        self.entries.truncate(self.k);
+        true
+    }
+
+    /// Number of discords currently held.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Consumes the buffer, returning its entries sorted by distance descending.
+    pub(crate) fn into_vec(self) -> Vec<(f64, usize)> {
+        self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RangeSet, TopKDiscords};
+
+    #[test]
+    fn range_set_merges_overlapping_and_touching_ranges() {
+        let mut set = RangeSet::new();
+        set.insert_range(10, 20);
+        set.insert_range(20, 30); // touches the first range, should merge
+        set.insert_range(50, 60); // disjoint
+
+        assert!(set.contains(15));
+        assert!(set.contains(25));
+        assert!(set.contains(55));
+        assert!(!set.contains(9));
+        assert!(!set.contains(30));
+        assert!(!set.contains(45));
+
+        set.insert_range(25, 55); // bridges the two remaining ranges
+        assert!(set.contains(40));
+    }
+
+    #[test]
+    fn top_k_discords_keeps_k_largest_and_rejects_overlap() {
+        let mut top = TopKDiscords::new(2, 10);
+
+        assert!(top.try_insert(5.0, 100));
+        assert!(top.try_insert(8.0, 200));
+        // Overlaps the accepted location at 200 (within discord_size of it).
+        assert!(!top.try_insert(9.0, 205));
+        // Smaller than the current worst kept entry once the buffer is full.
+        assert!(!top.try_insert(1.0, 300));
+        assert!(top.try_insert(9.0, 400));
+
+        let entries = top.into_vec();
+        assert_eq!(entries, vec![(9.0, 400), (8.0, 200)]);
+    }
 }
\ No newline at end of file
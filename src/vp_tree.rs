@@ -0,0 +1,148 @@
+use num::Float;
+use std::collections::HashMap;
+use rand::Rng;
+use crate::dist::{Proximity, Euclidean};
+
+/// A vantage-point tree over z-normalized subsequence windows, used to answer *exact*
+/// nearest-neighbor queries in better than `O(n)` time per query.
+///
+/// Unlike [`crate::hnsw::HnswIndex`], this preserves exact results: construction recursively
+/// partitions points around a vantage point, with the median distance to it becoming the
+/// radius `mu` that splits the remaining points into an "inside" subtree (`distance <= mu`)
+/// and an "outside" subtree (`distance > mu`). Queries maintain a running best radius `tau`
+/// and only descend into the far subtree when `|d - mu| < tau`, since the triangle inequality
+/// then guarantees nothing closer could be hiding there. This only holds for metrics that
+/// actually obey the triangle inequality, so `M` should be chosen accordingly (e.g.
+/// [`Euclidean`], but not `Dtw`).
+pub struct VpTree<N: Float, M: Proximity<N> = Euclidean> {
+    vectors: HashMap<usize, Vec<N>>,
+    nodes: Vec<VpNode>,
+    root: Option<usize>,
+    metric: M,
+}
+
+/// A single node: the vantage point `id`, the median distance `mu` used to split its
+/// remaining points, and the indexes (into `VpTree::nodes`) of the inside/outside subtrees.
+struct VpNode {
+    id: usize,
+    mu: f64,
+    inside: Option<usize>,
+    outside: Option<usize>,
+}
+
+impl<N: Float, M: Proximity<N>> VpTree<N, M> {
+    /// Builds a VP-tree over `points` (window start index paired with its z-normalized
+    /// vector), comparing windows with `metric`.
+    pub fn build(points: Vec<(usize, Vec<N>)>, metric: M) -> Self {
+        let vectors: HashMap<usize, Vec<N>> = points.iter().cloned().collect();
+        let ids: Vec<usize> = points.into_iter().map(|(id, _)| id).collect();
+
+        let mut tree = Self { vectors, nodes: Vec::new(), root: None, metric };
+        tree.root = tree.build_subtree(ids);
+        tree
+    }
+
+    fn distance(&self, a: usize, b: usize) -> f64 {
+        self.metric.distance(&self.vectors[&a], &self.vectors[&b]).to_f64().unwrap()
+    }
+
+    /// Recursively partitions `ids` around a randomly chosen vantage point, returning the
+    /// index of the resulting node in `self.nodes` (or `None` for an empty slice).
+    fn build_subtree(&mut self, mut ids: Vec<usize>) -> Option<usize> {
+        if ids.is_empty() {
+            return None;
+        }
+
+        let vp_pos = rand::thread_rng().gen_range(0..ids.len());
+        let vp = ids.swap_remove(vp_pos);
+
+        if ids.is_empty() {
+            self.nodes.push(VpNode { id: vp, mu: 0.0, inside: None, outside: None });
+            return Some(self.nodes.len() - 1);
+        }
+
+        let dists: Vec<f64> = ids.iter().map(|&id| self.distance(vp, id)).collect();
+        let mut sorted_dists = dists.clone();
+        sorted_dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mu = sorted_dists[sorted_dists.len() / 2];
+
+        let mut inside_ids = Vec::new();
+        let mut outside_ids = Vec::new();
+        for (id, d) in ids.into_iter().zip(dists.into_iter()) {
+            if d <= mu {
+                inside_ids.push(id);
+            } else {
+                outside_ids.push(id);
+            }
+        }
+
+        let inside = self.build_subtree(inside_ids);
+        let outside = self.build_subtree(outside_ids);
+
+        self.nodes.push(VpNode { id: vp, mu, inside, outside });
+        Some(self.nodes.len() - 1)
+    }
+
+    /// Finds the true nearest neighbor of the window at `query_id`, rejecting any candidate
+    /// within `exclude_radius` of it (the same trivial-match exclusion `HnswIndex` uses).
+    pub fn nearest_neighbor(&self, query_id: usize, exclude_radius: usize) -> Option<(f64, usize)> {
+        let root = self.root?;
+        let mut best: Option<(f64, usize)> = None;
+        self.search(root, query_id, exclude_radius, &mut best);
+        best
+    }
+
+    fn search(&self, node_idx: usize, query_id: usize, exclude_radius: usize, best: &mut Option<(f64, usize)>) {
+        let node = &self.nodes[node_idx];
+        let d = self.distance(query_id, node.id);
+        let trivial = (node.id as isize - query_id as isize).abs() < exclude_radius as isize;
+
+        if !trivial && best.map_or(true, |(bd, _)| d < bd) {
+            *best = Some((d, node.id));
+        }
+
+        // Visit whichever side `query` itself falls on first, since it's more likely to hold
+        // the nearest neighbor and tightens `tau` before the pruning check below.
+        let (near, far) = if d < node.mu {
+            (node.inside, node.outside)
+        } else {
+            (node.outside, node.inside)
+        };
+
+        if let Some(near) = near {
+            self.search(near, query_id, exclude_radius, best);
+        }
+
+        let tau = best.map_or(f64::INFINITY, |(bd, _)| bd);
+        if let Some(far) = far {
+            if (d - node.mu).abs() < tau {
+                self.search(far, query_id, exclude_radius, best);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VpTree;
+    use crate::dist::Euclidean;
+
+    #[test]
+    fn nearest_neighbor_matches_brute_force() {
+        let points: Vec<(usize, Vec<f64>)> = vec![
+            (0, vec![0.0, 0.0]),
+            (1, vec![1.0, 0.0]),
+            (2, vec![5.0, 5.0]),
+            (3, vec![5.0, 6.0]),
+            (4, vec![9.0, 9.0]),
+        ];
+
+        let tree = VpTree::build(points.clone(), Euclidean);
+
+        // Querying point 2 with a radius of 1 excludes itself (distance 0) and should find
+        // point 3, its true nearest neighbour by Euclidean distance among the rest.
+        let (dist, id) = tree.nearest_neighbor(2, 1).unwrap();
+        assert_eq!(id, 3);
+        assert!((dist - 1.0).abs() < 1e-9);
+    }
+}